@@ -0,0 +1,115 @@
+use proc_macro::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput, Fields, Member,
+    Token, Type,
+};
+
+/// Implements `Subject<FieldType>` for every field marked with `#[subject]`.
+#[proc_macro_derive(Subject, attributes(subject))]
+pub fn derive_subject(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let fields = match struct_fields(&ast) {
+        Ok(fields) => fields,
+        Err(err) => return err.into_compile_error().into(),
+    };
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let impls = fields
+        .iter()
+        .filter(|(_, field)| field.attrs.iter().any(|a| a.path().is_ident("subject")))
+        .map(|(member, field)| {
+            let ty = &field.ty;
+            quote! {
+                impl #impl_generics ::bevy_observer_pattern::Subject<#ty> for #name #ty_generics #where_clause {
+                    fn give_data(&self) -> &#ty {
+                        &self.#member
+                    }
+                }
+            }
+        });
+
+    quote!(#(#impls)*).into()
+}
+
+/// Implements `Observer<T>` for every `T` listed in `#[observe(T, ..)]` field attributes.
+/// Received data is cloned and converted into the field with `Into`, so an `Option<T>` field
+/// can observe `T`.
+#[proc_macro_derive(Observer, attributes(observe))]
+pub fn derive_observer(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let fields = match struct_fields(&ast) {
+        Ok(fields) => fields,
+        Err(err) => return err.into_compile_error().into(),
+    };
+
+    // Observed types in declaration order, each with the fields it is assigned to.
+    let mut observed: Vec<(Type, Vec<Member>)> = Vec::new();
+    for (member, field) in fields.iter() {
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("observe")) {
+            let types = match attr.parse_args_with(Punctuated::<Type, Token![,]>::parse_terminated)
+            {
+                Ok(types) => types,
+                Err(err) => return err.into_compile_error().into(),
+            };
+            for ty in types {
+                let key = ty.to_token_stream().to_string();
+                match observed
+                    .iter_mut()
+                    .find(|(t, _)| t.to_token_stream().to_string() == key)
+                {
+                    Some((_, members)) => members.push(member.clone()),
+                    None => observed.push((ty, vec![member.clone()])),
+                }
+            }
+        }
+    }
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let impls = observed.iter().map(|(ty, members)| {
+        quote! {
+            impl #impl_generics ::bevy_observer_pattern::Observer<#ty> for #name #ty_generics #where_clause {
+                type Param = ();
+
+                fn receive_data(
+                    &mut self,
+                    data: &#ty,
+                    _param: &mut (),
+                    _sender: ::bevy_observer_pattern::__private::Entity,
+                ) {
+                    #(self.#members = ::core::convert::Into::into(::core::clone::Clone::clone(data));)*
+                }
+            }
+        }
+    });
+
+    quote!(#(#impls)*).into()
+}
+
+fn struct_fields(ast: &DeriveInput) -> syn::Result<Vec<(Member, &syn::Field)>> {
+    let Data::Struct(data) = &ast.data else {
+        return Err(syn::Error::new(
+            ast.span(),
+            "Subject and Observer can only be derived for structs",
+        ));
+    };
+
+    Ok(match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|f| (Member::Named(f.ident.clone().unwrap()), f))
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (Member::Unnamed(i.into()), f))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    })
+}
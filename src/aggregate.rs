@@ -0,0 +1,475 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        reflect::ReflectMapEntities,
+        system::{Command, StaticSystemParam, SystemParam, SystemParamItem, SystemState},
+    },
+    prelude::*,
+    utils::HashSet,
+};
+
+use crate::{
+    insert_sorted, remove_sorted, scene, sorted_entities, ObserverLinkDropped, ObserverOrdering,
+    Subject,
+};
+
+/// An observer component that sees the data of all of its subjects at once.
+/// Whenever any subject changes or loses S, receive_all is called with every current subject,
+/// in the order they were linked.
+pub trait AggregateObserver<T: Send + Sync + 'static>: Component {
+    /// System params fetched alongside the observer and passed to receive_all.
+    type Param: SystemParam + 'static;
+
+    fn receive_all<'a>(
+        &mut self,
+        data: impl Iterator<Item = (Entity, &'a T)>,
+        param: &mut SystemParamItem<Self::Param>,
+    );
+}
+
+/// Subjects this entity aggregates, in the order they were linked.
+#[derive(Reflect, FromReflect, Clone, Component)]
+#[reflect(Component, MapEntities)]
+pub struct AggregateSubjects<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> {
+    subjects: Vec<Entity>,
+
+    #[reflect(ignore)]
+    unmapped: Vec<Entity>,
+
+    #[reflect(ignore)]
+    phantom_data: PhantomData<T>,
+
+    #[reflect(ignore)]
+    phantom_subject: PhantomData<S>,
+
+    #[reflect(ignore)]
+    phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> AggregateSubjects<T, S, O> {
+    pub fn new(list: impl IntoIterator<Item = Entity>) -> Self {
+        let mut subjects = AggregateSubjects {
+            subjects: Vec::new(),
+            unmapped: Vec::new(),
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        };
+        subjects.extend(list);
+        subjects
+    }
+
+    /// Adds subjects to the end of the list, skipping those already in it.
+    pub fn extend(&mut self, list: impl IntoIterator<Item = Entity>) {
+        for subject in list {
+            if !self.subjects.contains(&subject) {
+                self.subjects.push(subject);
+            }
+        }
+    }
+
+    /// Removes subjects from the list, keeping the order of the others.
+    pub fn remove(&mut self, list: &[Entity]) {
+        self.subjects.retain(|subject| !list.contains(subject));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.subjects.iter()
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> Default
+    for AggregateSubjects<T, S, O>
+{
+    fn default() -> Self {
+        AggregateSubjects::new(vec![])
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> MapEntities
+    for AggregateSubjects<T, S, O>
+{
+    fn map_entities(&mut self, m: &EntityMap) -> Result<(), MapEntitiesError> {
+        let (mapped, unmapped) = scene::map_links(self.subjects.drain(..), m);
+        self.subjects = mapped;
+        self.unmapped = unmapped;
+        Ok(())
+    }
+}
+
+/// Subjects kept by UnmappedLinkPolicy::Keep go to the end of the list.
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> scene::LinkEntities
+    for AggregateSubjects<T, S, O>
+{
+    const LINK: &'static str = "AggregateSubjects";
+
+    fn unmapped(&mut self) -> &mut Vec<Entity> {
+        &mut self.unmapped
+    }
+
+    fn keep_link(&mut self, entity: Entity) {
+        self.extend([entity]);
+    }
+}
+
+/// Aggregate observers linked to this subject entity, sorted like ObserverList.
+/// Kept in sync with the observers' AggregateSubjects by the aggregate observer commands,
+/// so a change to S only updates the observers linked to it.
+#[derive(Reflect, FromReflect, Clone, Component)]
+#[reflect(Component, MapEntities)]
+pub struct AggregatedBy<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> {
+    observers: Vec<Entity>,
+
+    #[reflect(ignore)]
+    unmapped: Vec<Entity>,
+
+    #[reflect(ignore)]
+    phantom_data: PhantomData<T>,
+
+    #[reflect(ignore)]
+    phantom_subject: PhantomData<S>,
+
+    #[reflect(ignore)]
+    phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> AggregatedBy<T, S, O> {
+    pub fn new(list: impl IntoIterator<Item = Entity>) -> Self {
+        AggregatedBy {
+            observers: sorted_entities(list),
+            unmapped: Vec::new(),
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.observers.iter()
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> Default
+    for AggregatedBy<T, S, O>
+{
+    fn default() -> Self {
+        AggregatedBy::new(vec![])
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> MapEntities
+    for AggregatedBy<T, S, O>
+{
+    fn map_entities(&mut self, m: &EntityMap) -> Result<(), MapEntitiesError> {
+        let (mapped, unmapped) = scene::map_links(self.observers.drain(..), m);
+        self.observers = sorted_entities(mapped);
+        self.unmapped = unmapped;
+        Ok(())
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> scene::LinkEntities
+    for AggregatedBy<T, S, O>
+{
+    const LINK: &'static str = "AggregatedBy";
+
+    fn unmapped(&mut self) -> &mut Vec<Entity> {
+        &mut self.unmapped
+    }
+
+    fn keep_link(&mut self, entity: Entity) {
+        insert_sorted(&mut self.observers, entity);
+    }
+}
+
+/// Gives the observer the data of every subject in the list that still has S.
+fn send_all<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+    observer: &mut O,
+    subjects: &[Entity],
+    subject_query: &Query<&S>,
+    param: &mut SystemParamItem<O::Param>,
+) {
+    let data = subjects.iter().filter_map(|&subject| {
+        subject_query
+            .get(subject)
+            .ok()
+            .map(|subject_comp| (subject, Subject::<T>::give_data(subject_comp)))
+    });
+    observer.receive_all(data, param);
+}
+
+/// Sends the observer the data of the subjects it currently aggregates.
+fn resend_all<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+    world: &mut World,
+    observer: Entity,
+) {
+    let subjects = world
+        .get::<AggregateSubjects<T, S, O>>(observer)
+        .map(|aggregate| aggregate.subjects.clone())
+        .unwrap_or_default();
+
+    let mut system_state: SystemState<(StaticSystemParam<O::Param>, Query<&mut O>, Query<&S>)> =
+        SystemState::new(world);
+
+    {
+        let (mut param, mut observer_query, subject_query) = system_state.get_mut(world);
+
+        if let Ok(mut observer) = observer_query.get_mut(observer) {
+            send_all::<T, S, O>(&mut observer, &subjects, &subject_query, &mut param);
+        }
+    }
+
+    system_state.apply(world);
+}
+
+/// Removes the observer from the subjects' AggregatedBy, dropping lists left empty.
+fn unlink_aggregate_observer<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+    world: &mut World,
+    observer: Entity,
+    subjects: impl IntoIterator<Item = Entity>,
+) {
+    for subject in subjects {
+        let Some(mut entity_mut) = world.get_entity_mut(subject) else {
+            continue;
+        };
+        let Some(mut aggregated_by) = entity_mut.get_mut::<AggregatedBy<T, S, O>>() else {
+            continue;
+        };
+        remove_sorted(&mut aggregated_by.observers, observer);
+        if aggregated_by.observers.is_empty() {
+            entity_mut.remove::<AggregatedBy<T, S, O>>();
+        }
+    }
+}
+
+/// Removes the subjects from the observer's AggregateSubjects, dropping the list when empty.
+fn unlink_aggregate_subjects<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+    world: &mut World,
+    observer: Entity,
+    subjects: &[Entity],
+) {
+    let Some(mut entity_mut) = world.get_entity_mut(observer) else {
+        return;
+    };
+    let Some(mut aggregate) = entity_mut.get_mut::<AggregateSubjects<T, S, O>>() else {
+        return;
+    };
+    aggregate.remove(subjects);
+    if aggregate.subjects.is_empty() {
+        entity_mut.remove::<AggregateSubjects<T, S, O>>();
+    }
+}
+
+pub(crate) struct AggregateObserverBuildCommand<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    O: AggregateObserver<T>,
+> {
+    pub observer: Entity,
+    pub subjects: Vec<Entity>,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<S>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> Command
+    for AggregateObserverBuildCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        for &source in self.subjects.iter() {
+            let Some(mut entity_mut) = world.get_entity_mut(source) else {
+                continue;
+            };
+            match entity_mut.get_mut::<AggregatedBy<T, S, O>>() {
+                Some(mut aggregated_by) => {
+                    insert_sorted(&mut aggregated_by.observers, self.observer);
+                }
+                None => {
+                    entity_mut.insert(AggregatedBy::<T, S, O>::new(vec![self.observer]));
+                }
+            }
+        }
+
+        let mut entity_mut = world.entity_mut(self.observer);
+        match entity_mut.get_mut::<AggregateSubjects<T, S, O>>() {
+            Some(mut aggregate) => aggregate.extend(self.subjects),
+            None => {
+                entity_mut.insert(AggregateSubjects::<T, S, O>::new(self.subjects));
+            }
+        }
+
+        resend_all::<T, S, O>(world, self.observer);
+    }
+}
+
+pub(crate) struct AggregateObserverRemoveCommand<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    O: AggregateObserver<T>,
+> {
+    pub observer: Entity,
+    pub subjects: Vec<Entity>,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<S>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> Command
+    for AggregateObserverRemoveCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        unlink_aggregate_observer::<T, S, O>(world, self.observer, self.subjects.iter().copied());
+        unlink_aggregate_subjects::<T, S, O>(world, self.observer, &self.subjects);
+        resend_all::<T, S, O>(world, self.observer);
+    }
+}
+
+pub(crate) struct AggregateObserverClearCommand<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    O: AggregateObserver<T>,
+> {
+    pub subject: Entity,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<S>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>> Command
+    for AggregateObserverClearCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        let Some(mut entity_mut) = world.get_entity_mut(self.subject) else {
+            return;
+        };
+        let Some(aggregated_by) = entity_mut.take::<AggregatedBy<T, S, O>>() else {
+            return;
+        };
+        for &observer in aggregated_by.observers.iter() {
+            unlink_aggregate_subjects::<T, S, O>(world, observer, &[self.subject]);
+            resend_all::<T, S, O>(world, observer);
+        }
+    }
+}
+
+/// Removes aggregate observers that lost their O component from every AggregatedBy.
+pub(crate) fn cleanup_removed_aggregate_observers<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    O: AggregateObserver<T>,
+>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<O>,
+    mut aggregated_by_query: Query<(Entity, &mut AggregatedBy<T, S, O>)>,
+    aggregate_query: Query<(), With<AggregateSubjects<T, S, O>>>,
+    mut dropped_events: EventWriter<ObserverLinkDropped>,
+) {
+    let removed: HashSet<Entity> = removed.iter().collect();
+    if removed.is_empty() {
+        return;
+    }
+
+    for (subject, mut aggregated_by) in aggregated_by_query.iter_mut() {
+        if !aggregated_by.observers.iter().any(|o| removed.contains(o)) {
+            continue;
+        }
+        for &observer in removed.iter() {
+            if remove_sorted(&mut aggregated_by.observers, observer) {
+                debug!(
+                    "Dropped stale aggregate link {:?} -> {:?}",
+                    subject, observer
+                );
+                dropped_events.send(ObserverLinkDropped { subject, observer });
+            }
+        }
+        if aggregated_by.observers.is_empty() {
+            commands.entity(subject).remove::<AggregatedBy<T, S, O>>();
+        }
+    }
+
+    for &observer in removed.iter() {
+        if aggregate_query.contains(observer) {
+            commands
+                .entity(observer)
+                .remove::<AggregateSubjects<T, S, O>>();
+        }
+    }
+}
+
+/// Drops subjects that were despawned or lost S from every AggregateSubjects,
+/// and updates the observers with the subjects they have left.
+pub(crate) fn prune_lost_aggregate_subjects<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    O: AggregateObserver<T>,
+>(
+    mut commands: Commands,
+    mut param: StaticSystemParam<O::Param>,
+    mut removed: RemovedComponents<S>,
+    subject_query: Query<&S>,
+    mut observer_query: Query<(Entity, &mut O, &mut AggregateSubjects<T, S, O>)>,
+    aggregated_by_query: Query<(), With<AggregatedBy<T, S, O>>>,
+) {
+    let removed: Vec<Entity> = removed.iter().collect();
+    if removed.is_empty() {
+        return;
+    }
+
+    for (observer, mut observer_comp, mut aggregate) in observer_query.iter_mut() {
+        if !aggregate.subjects.iter().any(|s| removed.contains(s)) {
+            continue;
+        }
+        aggregate.remove(&removed);
+        send_all::<T, S, O>(
+            &mut observer_comp,
+            &aggregate.subjects,
+            &subject_query,
+            &mut param,
+        );
+        if aggregate.subjects.is_empty() {
+            commands
+                .entity(observer)
+                .remove::<AggregateSubjects<T, S, O>>();
+        }
+    }
+
+    for &subject in removed.iter() {
+        if aggregated_by_query.contains(subject) {
+            commands.entity(subject).remove::<AggregatedBy<T, S, O>>();
+        }
+    }
+}
+
+/// Updates the aggregate observers linked to a subject that changed.
+pub(crate) fn recieve_aggregate_subject_event<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    O: AggregateObserver<T>,
+>(
+    mut param: StaticSystemParam<O::Param>,
+    changed_query: Query<&AggregatedBy<T, S, O>, Changed<S>>,
+    subject_query: Query<&S>,
+    mut observer_query: Query<(&mut O, &AggregateSubjects<T, S, O>)>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let observers: HashSet<Entity> = changed_query
+        .iter()
+        .flat_map(|aggregated_by| aggregated_by.observers.iter().copied())
+        .collect();
+    let mut observers: Vec<Entity> = observers.into_iter().collect();
+    ordering.sort_by_entity(&mut observers, |observer| *observer);
+
+    for observer in observers {
+        let Ok((mut observer_comp, aggregate)) = observer_query.get_mut(observer) else {
+            continue;
+        };
+        send_all::<T, S, O>(
+            &mut observer_comp,
+            &aggregate.subjects,
+            &subject_query,
+            &mut param,
+        );
+    }
+}
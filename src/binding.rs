@@ -0,0 +1,232 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::{GetPath, TypeRegistryInternal},
+};
+
+use crate::scene::{self, LinkEntities};
+
+/// Copies a field of a component on the subject entity into a field of a component on this
+/// entity, both named by strings, so bindings can be wired in scenes without new Rust types.
+/// Both components must be registered with `#[reflect(Component)]`.
+#[derive(Reflect, FromReflect, Clone, Debug)]
+pub struct ReflectBinding {
+    pub subject: Entity,
+    /// Component and field path on the subject, such as "Player.health".
+    pub source: String,
+    /// Component and field path on this entity, such as "Text.sections[0].value".
+    pub target: String,
+
+    /// Set once a failing binding has been warned about, so it doesn't warn every frame.
+    #[reflect(ignore)]
+    failed: bool,
+
+    /// Set when the subject was missing from the EntityMap, skipping the binding until the
+    /// UnmappedLinkPolicy drops or keeps it.
+    #[reflect(ignore)]
+    unmapped: bool,
+}
+
+impl ReflectBinding {
+    pub fn new(subject: Entity, source: impl Into<String>, target: impl Into<String>) -> Self {
+        ReflectBinding {
+            subject,
+            source: source.into(),
+            target: target.into(),
+            failed: false,
+            unmapped: false,
+        }
+    }
+}
+
+/// Reflection bindings whose targets are on this entity, applied in order every frame
+/// by the system added with register_reflect_bindings.
+/// A target field only changes when the value it would receive differs from its own.
+/// Values bound to a String field of another type are formatted with Debug.
+#[derive(Reflect, FromReflect, Clone, Default, Debug, Component)]
+#[reflect(Component, MapEntities)]
+pub struct ReflectBindings {
+    pub bindings: Vec<ReflectBinding>,
+
+    #[reflect(ignore)]
+    unmapped: Vec<Entity>,
+}
+
+impl ReflectBindings {
+    pub fn new(bindings: impl IntoIterator<Item = ReflectBinding>) -> Self {
+        ReflectBindings {
+            bindings: bindings.into_iter().collect(),
+            unmapped: Vec::new(),
+        }
+    }
+}
+
+/// Bindings to subjects missing from the map keep their place in the list,
+/// but are skipped until the UnmappedLinkPolicy drops or keeps them.
+impl MapEntities for ReflectBindings {
+    fn map_entities(&mut self, m: &EntityMap) -> Result<(), MapEntitiesError> {
+        for binding in self.bindings.iter_mut() {
+            let (mapped, unmapped) = scene::map_links([binding.subject], m);
+            match mapped.first() {
+                Some(&subject) => binding.subject = subject,
+                None => {
+                    binding.unmapped = true;
+                    if !self.unmapped.contains(&binding.subject) {
+                        self.unmapped.extend(unmapped);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LinkEntities for ReflectBindings {
+    const LINK: &'static str = "ReflectBinding";
+
+    fn unmapped(&mut self) -> &mut Vec<Entity> {
+        &mut self.unmapped
+    }
+
+    fn keep_link(&mut self, entity: Entity) {
+        for binding in self.bindings.iter_mut() {
+            if binding.subject == entity {
+                binding.unmapped = false;
+            }
+        }
+    }
+
+    fn drop_link(&mut self, entity: Entity) {
+        self.bindings
+            .retain(|binding| !(binding.unmapped && binding.subject == entity));
+    }
+}
+
+/// Splits "Component.path" into the component name and the field path.
+fn split_path(path: &str) -> (&str, &str) {
+    path.split_once('.').unwrap_or((path, ""))
+}
+
+fn reflect_component<'a>(
+    registry: &'a TypeRegistryInternal,
+    name: &str,
+) -> Result<&'a ReflectComponent, String> {
+    registry
+        .get_with_short_name(name)
+        .or_else(|| registry.get_with_name(name))
+        .ok_or_else(|| format!("{} is not registered", name))?
+        .data::<ReflectComponent>()
+        .ok_or_else(|| format!("{} is not registered as a reflected component", name))
+}
+
+/// Copies the source value into the target field, returning whether the target changed.
+fn apply_value(target: &mut dyn Reflect, value: &dyn Reflect) -> Result<bool, String> {
+    if target.reflect_partial_eq(value) == Some(true) {
+        return Ok(false);
+    }
+    if target.type_name() == value.type_name() {
+        target.apply(value);
+        return Ok(true);
+    }
+    match target.downcast_mut::<String>() {
+        Some(text) => {
+            let formatted = format!("{:?}", value);
+            let changed = *text != formatted;
+            *text = formatted;
+            Ok(changed)
+        }
+        None => Err(format!(
+            "can't bind {} to {}",
+            value.type_name(),
+            target.type_name()
+        )),
+    }
+}
+
+fn apply_binding(
+    world: &mut World,
+    registry: &TypeRegistryInternal,
+    entity: Entity,
+    binding: &ReflectBinding,
+) -> Result<(), String> {
+    let (source_name, source_path) = split_path(&binding.source);
+    let (target_name, target_path) = split_path(&binding.target);
+    let source_component = reflect_component(registry, source_name)?;
+    let target_component = reflect_component(registry, target_name)?;
+
+    let subject = world
+        .get_entity(binding.subject)
+        .ok_or_else(|| format!("subject {:?} doesn't exist", binding.subject))?;
+    let source = source_component
+        .reflect(subject)
+        .ok_or_else(|| format!("subject {:?} has no {}", binding.subject, source_name))?;
+    let value = match source_path.is_empty() {
+        true => source.clone_value(),
+        false => source
+            .reflect_path(source_path)
+            .map_err(|err| err.to_string())?
+            .clone_value(),
+    };
+
+    let mut entity_mut = world.entity_mut(entity);
+    let mut target = target_component
+        .reflect_mut(&mut entity_mut)
+        .ok_or_else(|| format!("{:?} has no {}", entity, target_name))?;
+    let changed = {
+        let target = target.bypass_change_detection();
+        let field = match target_path.is_empty() {
+            true => target,
+            false => target
+                .reflect_path_mut(target_path)
+                .map_err(|err| err.to_string())?,
+        };
+        apply_value(field, &*value)?
+    };
+    if changed {
+        target.set_changed();
+    }
+
+    Ok(())
+}
+
+/// Applies every ReflectBinding, warning once about each binding that fails.
+pub(crate) fn apply_reflect_bindings(world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    let entities: Vec<(Entity, Vec<ReflectBinding>)> = world
+        .query::<(Entity, &ReflectBindings)>()
+        .iter(world)
+        .map(|(entity, bindings)| (entity, bindings.bindings.clone()))
+        .collect();
+
+    for (entity, bindings) in entities {
+        for (index, binding) in bindings.iter().enumerate() {
+            if binding.unmapped {
+                continue;
+            }
+            let failed = match apply_binding(world, &registry, entity, binding) {
+                Ok(()) => false,
+                Err(err) => {
+                    if !binding.failed {
+                        warn!(
+                            "Binding {} -> {} of {:?} failed: {}",
+                            binding.source, binding.target, entity, err
+                        );
+                    }
+                    true
+                }
+            };
+            if failed != binding.failed {
+                if let Some(mut bindings) = world.get_mut::<ReflectBindings>(entity) {
+                    if let Some(binding) = bindings.bindings.get_mut(index) {
+                        binding.failed = failed;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use bevy::{
+    asset::{AssetLoader, HandleId, LoadContext, LoadedAsset},
+    ecs::system::Command,
+    prelude::*,
+    reflect::TypeUuid,
+    utils::{BoxedFuture, HashMap},
+};
+use serde::Deserialize;
+
+use crate::scene::{named_link_type, SceneLinkType};
+
+/// Links between entities described in a `.bindings.ron` file.
+/// Every loaded BindingSet is applied when it loads, and its links are replaced when it
+/// hot-reloads or removed when it is unloaded, so keep a strong handle to it for as long as
+/// its links should exist.
+/// Selectors are resolved when the set is applied, so entities spawned later aren't linked.
+///
+/// ```ron
+/// (
+///     links: [
+///         (
+///             link: "Health -> HealthBar: f32",
+///             observers: [Name("PlayerHealthBar")],
+///             subjects: [Marker("Player")],
+///         ),
+///     ],
+/// )
+/// ```
+#[derive(Deserialize, TypeUuid, Clone, Debug, Default)]
+#[uuid = "e38daccb-05ec-433b-849b-29258bc7f8b5"]
+pub struct BindingSet {
+    pub links: Vec<BindingSetLink>,
+}
+
+/// Links every observer entity to every subject entity through one registered link type.
+#[derive(Deserialize, Clone, Debug)]
+pub struct BindingSetLink {
+    /// Name the link type was registered under, as written in SceneLinks.
+    pub link: String,
+    pub observers: Vec<EntitySelector>,
+    pub subjects: Vec<EntitySelector>,
+}
+
+/// Finds entities for a BindingSet.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum EntitySelector {
+    /// Every entity with this Name.
+    Name(String),
+    /// Every entity with this component, registered with `#[reflect(Component)]`.
+    Marker(String),
+}
+
+impl EntitySelector {
+    /// Matching entities, sorted.
+    pub fn select(&self, world: &mut World) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = match self {
+            EntitySelector::Name(name) => world
+                .query::<(Entity, &Name)>()
+                .iter(world)
+                .filter(|(_, entity_name)| entity_name.as_str() == name)
+                .map(|(entity, _)| entity)
+                .collect(),
+            EntitySelector::Marker(marker) => {
+                let registry = world.resource::<AppTypeRegistry>().clone();
+                let registry = registry.read();
+                let Some(reflect_component) = registry
+                    .get_with_short_name(marker)
+                    .or_else(|| registry.get_with_name(marker))
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                else {
+                    warn!(
+                        "Marker {} is not registered as a reflected component",
+                        marker
+                    );
+                    return Vec::new();
+                };
+                let entities: Vec<Entity> = world.query::<Entity>().iter(world).collect();
+                entities
+                    .into_iter()
+                    .filter(|&entity| reflect_component.reflect(world.entity(entity)).is_some())
+                    .collect()
+            }
+        };
+        entities.sort();
+        entities
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct BindingSetLoader;
+
+impl AssetLoader for BindingSetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let binding_set = ron::de::from_bytes::<BindingSet>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(binding_set));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bindings.ron"]
+    }
+}
+
+/// Links made by each applied BindingSet, so they can be removed again.
+/// Links that already existed when a set was applied aren't recorded, so removing the set
+/// leaves them in place.
+#[derive(Resource, Default)]
+pub(crate) struct AppliedBindingSets {
+    links: HashMap<HandleId, Vec<(SceneLinkType, Entity, Vec<Entity>)>>,
+}
+
+/// Links in `after` that aren't in `before`, both as given by SceneLinkType::store.
+/// An entity missing from `before` had no link of the type at all, which is how links without
+/// subjects, such as resource links, show up as created.
+fn created_links(
+    before: Vec<(Entity, Vec<Entity>)>,
+    after: Vec<(Entity, Vec<Entity>)>,
+) -> Vec<(Entity, Vec<Entity>)> {
+    let before: HashMap<Entity, Vec<Entity>> = before.into_iter().collect();
+    after
+        .into_iter()
+        .filter_map(|(entity, subjects)| match before.get(&entity) {
+            None => Some((entity, subjects)),
+            Some(existing) => {
+                let subjects: Vec<Entity> = subjects
+                    .into_iter()
+                    .filter(|subject| !existing.contains(subject))
+                    .collect();
+                (!subjects.is_empty()).then_some((entity, subjects))
+            }
+        })
+        .collect()
+}
+
+struct RemoveBindingSetCommand {
+    handle: HandleId,
+}
+
+impl Command for RemoveBindingSetCommand {
+    fn write(self, world: &mut World) {
+        let links = world
+            .get_resource_mut::<AppliedBindingSets>()
+            .and_then(|mut applied| applied.links.remove(&self.handle))
+            .unwrap_or_default();
+        for (link_type, observer, subjects) in links {
+            if world.get_entity(observer).is_none() {
+                continue;
+            }
+            let subjects = subjects
+                .into_iter()
+                .filter(|&subject| world.get_entity(subject).is_some())
+                .collect();
+            (link_type.remove)(world, observer, subjects);
+        }
+    }
+}
+
+struct ApplyBindingSetCommand {
+    handle: HandleId,
+    binding_set: BindingSet,
+}
+
+impl Command for ApplyBindingSetCommand {
+    fn write(self, world: &mut World) {
+        RemoveBindingSetCommand {
+            handle: self.handle,
+        }
+        .write(world);
+
+        let mut applied = Vec::new();
+        for link in self.binding_set.links {
+            let Some(link_type) = named_link_type(world, &link.link) else {
+                warn!(
+                    "Skipped binding {}, no link type is registered under that name",
+                    link.link
+                );
+                continue;
+            };
+
+            let observers: Vec<Entity> = link
+                .observers
+                .iter()
+                .flat_map(|selector| selector.select(world))
+                .collect();
+            let subjects: Vec<Entity> = link
+                .subjects
+                .iter()
+                .flat_map(|selector| selector.select(world))
+                .collect();
+            let before = (link_type.store)(world);
+            for observer in observers {
+                (link_type.restore)(world, observer, subjects.clone());
+            }
+            let after = (link_type.store)(world);
+            applied.extend(
+                created_links(before, after)
+                    .into_iter()
+                    .map(|(entity, subjects)| (link_type, entity, subjects)),
+            );
+        }
+
+        world
+            .get_resource_or_insert_with(AppliedBindingSets::default)
+            .links
+            .insert(self.handle, applied);
+    }
+}
+
+/// Applies binding sets as they load or hot-reload, and removes their links when unloaded.
+pub(crate) fn apply_binding_sets(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<BindingSet>>,
+    binding_sets: Res<Assets<BindingSet>>,
+) {
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if let Some(binding_set) = binding_sets.get(handle) {
+                    commands.add(ApplyBindingSetCommand {
+                        handle: handle.id(),
+                        binding_set: binding_set.clone(),
+                    });
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                commands.add(RemoveBindingSetCommand {
+                    handle: handle.id(),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::system::{Command, StaticSystemParam, SystemState},
+    prelude::*,
+};
+
+use crate::{ComputedSubject, Observer, ObserverList, ObserverOrdering};
+
+/// Links an observer to computed subjects through the regular ObserverList,
+/// sending the value from compute_data instead of give_data.
+pub(crate) struct ComputedObserverBuildCommand<
+    T: Send + Sync + 'static,
+    S: ComputedSubject<T>,
+    O: Observer<T>,
+> {
+    pub observer: Entity,
+    pub subjects: Vec<Entity>,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<S>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: ComputedSubject<T>, O: Observer<T>> Command
+    for ComputedObserverBuildCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        crate::link_subjects::<T, S, O>(world, self.observer, &self.subjects);
+
+        let mut subjects = self.subjects;
+        if let Some(ordering) = world.get_resource::<ObserverOrdering>() {
+            ordering.sort_by_entity(&mut subjects, |subject| *subject);
+        }
+
+        let mut system_state: SystemState<(
+            StaticSystemParam<O::Param>,
+            Query<&mut O>,
+            Query<(Entity, &S)>,
+        )> = SystemState::new(world);
+
+        {
+            let (mut param, mut observer_query, subject_query) = system_state.get_mut(world);
+
+            if let Ok(mut observer) = observer_query.get_mut(self.observer) {
+                for &source in subjects.iter() {
+                    if let Ok((subject, subject_comp)) = subject_query.get(source) {
+                        let data = subject_comp.compute_data();
+                        observer.receive_data(&data, &mut param, subject)
+                    }
+                }
+            }
+        }
+
+        system_state.apply(world);
+    }
+}
+
+/// Links the observer to the computed subjects with an initial sync, such as when restoring
+/// scene links.
+pub(crate) fn restore_computed_observer<
+    T: Send + Sync + 'static,
+    S: ComputedSubject<T>,
+    O: Observer<T>,
+>(
+    world: &mut World,
+    observer: Entity,
+    subjects: Vec<Entity>,
+) {
+    ComputedObserverBuildCommand::<T, S, O> {
+        observer,
+        subjects,
+        phantom_data: PhantomData,
+        phantom_subject: PhantomData,
+        phantom_observer: PhantomData,
+    }
+    .write(world)
+}
+
+/// Computes the value of every changed computed subject once and sends it to its observers.
+pub(crate) fn recieve_computed_subject_event<
+    T: Send + Sync + 'static,
+    S: ComputedSubject<T>,
+    O: Observer<T>,
+>(
+    mut param: StaticSystemParam<O::Param>,
+    mut observer_query: Query<&mut O>,
+    mut observer_list_query: Query<(Entity, &S, &mut ObserverList<T, S, O>), Changed<S>>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let mut changed: Vec<_> = observer_list_query.iter_mut().collect();
+    ordering.sort_by_entity(&mut changed, |(subject, ..)| *subject);
+
+    for (subject, subject_comp, mut observer_list) in changed {
+        let data = subject_comp.compute_data();
+        crate::send_to_observers(
+            &data,
+            subject,
+            &mut observer_list,
+            &mut observer_query,
+            &mut param,
+            *ordering,
+        );
+    }
+}
+
+/// Same as recieve_computed_subject_event, but skips subjects whose computed value
+/// equals the last value sent.
+pub(crate) fn recieve_diffed_computed_subject_event<
+    T: PartialEq + Clone + Send + Sync + 'static,
+    S: ComputedSubject<T>,
+    O: Observer<T>,
+>(
+    mut param: StaticSystemParam<O::Param>,
+    mut observer_query: Query<&mut O>,
+    mut observer_list_query: Query<(Entity, &S, &mut ObserverList<T, S, O>), Changed<S>>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let mut changed: Vec<_> = observer_list_query.iter_mut().collect();
+    ordering.sort_by_entity(&mut changed, |(subject, ..)| *subject);
+
+    for (subject, subject_comp, mut observer_list) in changed {
+        let data = subject_comp.compute_data();
+        if observer_list.last_sent.as_ref() == Some(&data) {
+            continue;
+        }
+        crate::send_to_observers(
+            &data,
+            subject,
+            &mut observer_list,
+            &mut observer_query,
+            &mut param,
+            *ordering,
+        );
+        observer_list.last_sent = Some(data);
+    }
+}
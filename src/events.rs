@@ -0,0 +1,40 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::{ObserverOrdering, Subject};
+
+/// Sent whenever component S changes on an entity, for systems that want to react to
+/// subject changes without owning an observer component.
+/// Enabled per (T, S) pair with register_subject_events.
+pub struct SubjectChanged<T: Clone + Send + Sync + 'static, S: Subject<T>> {
+    pub subject: Entity,
+    pub data: T,
+
+    phantom_subject: PhantomData<S>,
+}
+
+impl<T: Clone + Send + Sync + 'static, S: Subject<T>> SubjectChanged<T, S> {
+    pub fn new(subject: Entity, data: T) -> Self {
+        SubjectChanged {
+            subject,
+            data,
+            phantom_subject: PhantomData,
+        }
+    }
+}
+
+/// Sends a SubjectChanged event for every changed S.
+pub(crate) fn send_subject_events<T: Clone + Send + Sync + 'static, S: Subject<T>>(
+    subject_query: Query<(Entity, &S), Changed<S>>,
+    mut events: EventWriter<SubjectChanged<T, S>>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let mut changed: Vec<_> = subject_query.iter().collect();
+    ordering.sort_by_entity(&mut changed, |(subject, _)| *subject);
+
+    for (subject, subject_comp) in changed {
+        let data = Subject::<T>::give_data(subject_comp);
+        events.send(SubjectChanged::new(subject, data.clone()));
+    }
+}
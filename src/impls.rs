@@ -1,12 +1,30 @@
+#[cfg(feature = "transform")]
+pub(crate) use transform::write_follows;
+#[cfg(feature = "transform")]
+pub use transform::Follow;
+#[cfg(feature = "bevy_ui")]
+pub use ui::{format_template, StyleDimension, StyleRatio, TextFormat};
+#[cfg(feature = "bevy_ui")]
+pub(crate) use ui::{write_style_ratios, write_text_formats};
+
 #[cfg(feature = "bevy_ui")]
 mod ui {
+    use std::fmt::Display;
+
+    use bevy::{
+        prelude::*,
+        text::{Text, TextSection},
+    };
+
     use crate::Observer;
 
     impl Observer<String> for bevy::ui::UiImage {
+        type Param = bevy::prelude::Res<'static, bevy::prelude::AssetServer>;
+
         fn receive_data(
             &mut self,
             data: &String,
-            asset_server: &bevy::prelude::Res<bevy::prelude::AssetServer>,
+            asset_server: &mut bevy::prelude::Res<bevy::prelude::AssetServer>,
             _sender: bevy::prelude::Entity,
         ) {
             self.texture = asset_server.load(data);
@@ -14,10 +32,12 @@ mod ui {
     }
 
     impl Observer<bevy::prelude::Handle<bevy::prelude::Image>> for bevy::ui::UiImage {
+        type Param = ();
+
         fn receive_data(
             &mut self,
             data: &bevy::prelude::Handle<bevy::prelude::Image>,
-            _asset_server: &bevy::prelude::Res<bevy::prelude::AssetServer>,
+            _param: &mut (),
             _sender: bevy::prelude::Entity,
         ) {
             self.texture = data.clone();
@@ -25,13 +45,391 @@ mod ui {
     }
 
     impl Observer<bevy::prelude::Color> for bevy::ui::BackgroundColor {
+        type Param = ();
+
         fn receive_data(
             &mut self,
             data: &bevy::prelude::Color,
-            _asset_server: &bevy::prelude::Res<bevy::prelude::AssetServer>,
+            _param: &mut (),
             _sender: bevy::prelude::Entity,
         ) {
             self.0 = *data;
         }
     }
+
+    /// Writes the value into the first section of the Text, adding one if it has none.
+    fn set_first_section(text: &mut Text, value: String) {
+        match text.sections.first_mut() {
+            Some(section) => section.value = value,
+            None => text
+                .sections
+                .push(TextSection::new(value, TextStyle::default())),
+        }
+    }
+
+    // Text observers write into the first section. To write into another section, observe with
+    // TextFormat::section instead, whose plain "{}" template writes the value as it is.
+    macro_rules! impl_text_observer {
+        ($($ty:ty),*) => {$(
+            impl Observer<$ty> for Text {
+                type Param = ();
+
+                fn receive_data(&mut self, data: &$ty, _param: &mut (), _sender: Entity) {
+                    set_first_section(self, data.to_string());
+                }
+            }
+
+            impl Observer<$ty> for TextFormat {
+                type Param = ();
+
+                fn receive_data(&mut self, data: &$ty, _param: &mut (), _sender: Entity) {
+                    self.set_args(&[data]);
+                }
+            }
+        )*};
+    }
+
+    impl_text_observer!(
+        String, bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32,
+        f64
+    );
+
+    /// Fills each `{}` in the template with the next argument.
+    /// Placeholders without an argument are left as they are, and extra arguments are ignored.
+    pub fn format_template(template: &str, args: &[&dyn Display]) -> String {
+        let mut formatted = String::new();
+        let mut args = args.iter();
+        let mut rest = template;
+        while let Some(index) = rest.find("{}") {
+            formatted.push_str(&rest[..index]);
+            match args.next() {
+                Some(arg) => formatted.push_str(&arg.to_string()),
+                None => formatted.push_str("{}"),
+            }
+            rest = &rest[index + 2..];
+        }
+        formatted.push_str(rest);
+        formatted
+    }
+
+    /// Formats observed values into a template like "HP: {}/{}" and writes the result into
+    /// a section of the Text on the same entity.
+    /// Tuples fill the placeholders in order, so "HP: {}/{}" can observe (current, max)
+    /// mapped from a subject with set_observer_with.
+    /// Requires register_text_format.
+    #[derive(Component, Reflect, FromReflect, Clone, Debug)]
+    #[reflect(Component)]
+    pub struct TextFormat {
+        pub template: String,
+        /// Index of the Text section written to.
+        pub section: usize,
+
+        #[reflect(ignore)]
+        formatted: Option<String>,
+    }
+
+    impl TextFormat {
+        pub fn new(template: impl Into<String>) -> Self {
+            TextFormat {
+                template: template.into(),
+                section: 0,
+                formatted: None,
+            }
+        }
+
+        /// Writes observed values as they are into the given section of the Text.
+        /// Several values can go into one section by observing a tuple with a template.
+        pub fn section(section: usize) -> Self {
+            TextFormat::new("{}").with_section(section)
+        }
+
+        pub fn with_section(mut self, section: usize) -> Self {
+            self.section = section;
+            self
+        }
+
+        /// The template filled with the last values received, if any were.
+        pub fn formatted(&self) -> Option<&str> {
+            self.formatted.as_deref()
+        }
+
+        fn set_args(&mut self, args: &[&dyn Display]) {
+            self.formatted = Some(format_template(&self.template, args));
+        }
+    }
+
+    impl Default for TextFormat {
+        fn default() -> Self {
+            TextFormat::new("{}")
+        }
+    }
+
+    macro_rules! impl_text_format_tuple {
+        ($($name:ident),*) => {
+            impl<$($name: Display + Send + Sync + 'static),*> Observer<($($name,)*)> for TextFormat {
+                type Param = ();
+
+                #[allow(non_snake_case)]
+                fn receive_data(
+                    &mut self,
+                    data: &($($name,)*),
+                    _param: &mut (),
+                    _sender: Entity,
+                ) {
+                    let ($($name,)*) = data;
+                    self.set_args(&[$($name as &dyn Display),*]);
+                }
+            }
+        };
+    }
+
+    impl_text_format_tuple!(A);
+    impl_text_format_tuple!(A, B);
+    impl_text_format_tuple!(A, B, C);
+    impl_text_format_tuple!(A, B, C, D);
+
+    /// Writes changed TextFormats into their Text section.
+    pub(crate) fn write_text_formats(
+        mut query: Query<(&TextFormat, &mut Text), Changed<TextFormat>>,
+    ) {
+        for (format, mut text) in query.iter_mut() {
+            let Some(formatted) = format.formatted() else {
+                continue;
+            };
+            let Some(section) = text.sections.get(format.section) else {
+                warn!(
+                    "TextFormat writes to section {} of a Text with {} sections",
+                    format.section,
+                    text.sections.len()
+                );
+                continue;
+            };
+            if section.value != formatted {
+                text.sections[format.section].value = formatted.to_string();
+            }
+        }
+    }
+
+    /// Shows the node on true and hides it on false.
+    impl Observer<bool> for Visibility {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &bool, _param: &mut (), _sender: Entity) {
+            *self = match data {
+                true => Visibility::Inherited,
+                false => Visibility::Hidden,
+            };
+        }
+    }
+
+    /// Lays the node out on true and removes it from the layout on false.
+    impl Observer<bool> for Style {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &bool, _param: &mut (), _sender: Entity) {
+            self.display = match data {
+                true => bevy::ui::Display::Flex,
+                false => bevy::ui::Display::None,
+            };
+        }
+    }
+
+    /// Sets the index, keeping whether it is local or global.
+    impl Observer<i32> for ZIndex {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &i32, _param: &mut (), _sender: Entity) {
+            *self = match self {
+                ZIndex::Local(_) => ZIndex::Local(*data),
+                ZIndex::Global(_) => ZIndex::Global(*data),
+            };
+        }
+    }
+
+    /// Style value written by a StyleRatio.
+    #[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum StyleDimension {
+        #[default]
+        Width,
+        Height,
+        Left,
+        Right,
+        Top,
+        Bottom,
+    }
+
+    impl StyleDimension {
+        fn val_mut(self, style: &mut Style) -> &mut Val {
+            match self {
+                StyleDimension::Width => &mut style.size.width,
+                StyleDimension::Height => &mut style.size.height,
+                StyleDimension::Left => &mut style.position.left,
+                StyleDimension::Right => &mut style.position.right,
+                StyleDimension::Top => &mut style.position.top,
+                StyleDimension::Bottom => &mut style.position.bottom,
+            }
+        }
+    }
+
+    /// Maps an observed ratio between 0 and 1 onto a percentage of a dimension of the Style on
+    /// the same entity, such as the width of a health bar.
+    /// Ratios outside 0..1 are clamped.
+    /// Requires register_style_ratio.
+    #[derive(Component, Reflect, FromReflect, Clone, Debug, Default)]
+    #[reflect(Component)]
+    pub struct StyleRatio {
+        pub dimension: StyleDimension,
+        pub ratio: f32,
+    }
+
+    impl StyleRatio {
+        pub fn new(dimension: StyleDimension) -> Self {
+            StyleRatio {
+                dimension,
+                ratio: 0.0,
+            }
+        }
+    }
+
+    impl Observer<f32> for StyleRatio {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &f32, _param: &mut (), _sender: Entity) {
+            self.ratio = data.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Writes changed StyleRatios into their Style.
+    pub(crate) fn write_style_ratios(
+        mut query: Query<(&StyleRatio, &mut Style), Changed<StyleRatio>>,
+    ) {
+        for (style_ratio, mut style) in query.iter_mut() {
+            let val = Val::Percent(style_ratio.ratio * 100.0);
+            if *style_ratio
+                .dimension
+                .val_mut(style.bypass_change_detection())
+                != val
+            {
+                *style_ratio.dimension.val_mut(&mut style) = val;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bevy_sprite")]
+mod sprite {
+    use bevy::prelude::*;
+
+    use crate::Observer;
+
+    impl Observer<Color> for Sprite {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &Color, _param: &mut (), _sender: Entity) {
+            self.color = *data;
+        }
+    }
+
+    impl Observer<Color> for TextureAtlasSprite {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &Color, _param: &mut (), _sender: Entity) {
+            self.color = *data;
+        }
+    }
+
+    impl Observer<usize> for TextureAtlasSprite {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &usize, _param: &mut (), _sender: Entity) {
+            self.index = *data;
+        }
+    }
+
+    /// Loads the image at the received asset path.
+    impl Observer<String> for Handle<Image> {
+        type Param = Res<'static, AssetServer>;
+
+        fn receive_data(
+            &mut self,
+            data: &String,
+            asset_server: &mut Res<AssetServer>,
+            _sender: Entity,
+        ) {
+            *self = asset_server.load(data);
+        }
+    }
+}
+
+#[cfg(feature = "transform")]
+mod transform {
+    use bevy::prelude::*;
+
+    use crate::Observer;
+
+    /// Moves the Transform on the same entity to the observed translation plus an offset,
+    /// such as a camera following the player or a nameplate above a unit.
+    /// Transform doesn't observe Transform directly, since the propagation system would then
+    /// borrow it both as subject and observer.
+    /// GlobalTransform is propagated during CoreSet::PostUpdate, so following it lags a frame.
+    /// Requires register_follow.
+    #[derive(Component, Reflect, FromReflect, Clone, Debug, Default)]
+    #[reflect(Component)]
+    pub struct Follow {
+        pub offset: Vec3,
+
+        #[reflect(ignore)]
+        target: Option<Vec3>,
+    }
+
+    impl Follow {
+        pub fn new(offset: Vec3) -> Self {
+            Follow {
+                offset,
+                target: None,
+            }
+        }
+
+        /// Translation of the followed subject, once it has been received.
+        pub fn target(&self) -> Option<Vec3> {
+            self.target
+        }
+    }
+
+    impl Observer<Transform> for Follow {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &Transform, _param: &mut (), _sender: Entity) {
+            self.target = Some(data.translation);
+        }
+    }
+
+    impl Observer<GlobalTransform> for Follow {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &GlobalTransform, _param: &mut (), _sender: Entity) {
+            self.target = Some(data.translation());
+        }
+    }
+
+    impl Observer<Vec3> for Follow {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &Vec3, _param: &mut (), _sender: Entity) {
+            self.target = Some(*data);
+        }
+    }
+
+    /// Moves the Transform of every changed Follow.
+    pub(crate) fn write_follows(mut query: Query<(&Follow, &mut Transform), Changed<Follow>>) {
+        for (follow, mut transform) in query.iter_mut() {
+            let Some(target) = follow.target else {
+                continue;
+            };
+            let translation = target + follow.offset;
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
+        }
+    }
 }
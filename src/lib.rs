@@ -1,32 +1,83 @@
 use std::{
+    any::{type_name, TypeId},
     marker::PhantomData,
-    ops::{Deref, DerefMut},
+    ops::Deref,
+    sync::Arc,
 };
 
 use bevy::{
+    app::SystemAppConfig,
     ecs::{
         entity::{EntityMap, MapEntities, MapEntitiesError},
         query::QueryEntityError,
         reflect::ReflectMapEntities,
-        system::{Command, EntityCommands, SystemState},
+        schedule::{ScheduleLabel, SystemConfig},
+        system::{
+            Command, EntityCommands, StaticSystemParam, SystemParam, SystemParamItem, SystemState,
+        },
         world::EntityMut,
     },
     prelude::*,
     utils::HashSet,
 };
 
+mod aggregate;
+mod binding;
+mod binding_set;
+mod computed;
+mod events;
 mod impls;
+mod mapped;
+mod registry;
+mod resource;
+mod scene;
+mod two_way;
+
+pub use aggregate::{AggregateObserver, AggregateSubjects, AggregatedBy};
+pub use binding::{ReflectBinding, ReflectBindings};
+pub use binding_set::{BindingSet, BindingSetLink, EntitySelector};
+pub use events::SubjectChanged;
+#[cfg(feature = "transform")]
+pub use impls::Follow;
+#[cfg(feature = "bevy_ui")]
+pub use impls::{format_template, StyleDimension, StyleRatio, TextFormat};
+pub use mapped::{MapFn, MappedObserverList, MappedObservingList};
+pub use registry::{ComponentInfo, ObserverRegistry, RegisteredLink};
+pub use resource::{
+    ObservedByResource, ObservingResource, ResourceObserver, ResourceObserverCommandExt,
+    ResourceSubject, RESOURCE_SUBJECT,
+};
+pub use scene::{SceneLink, SceneLinks, SceneLinksExt, UnmappedLinkPolicy};
+
+#[cfg(feature = "derive")]
+pub use bevy_observer_pattern_derive::{Observer, Subject};
+
+// Lets the derive macros refer to this crate by name from inside its own tests.
+#[cfg(feature = "derive")]
+extern crate self as bevy_observer_pattern;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use bevy::prelude::Entity;
+}
 
 // Implementation of the observer pattern between components on entities.
-// Observers will be given a reference to the subject, a reference to the asset server,
-// and the subject's entity.
+// Observers will be given a reference to the subject, the system params they declare
+// (for example the asset server), and the subject's entity.
 // Register every subject type with register_subject when building app.
 // Register every observer AND the subject type with register_observer when building app.
 // Call set_observer when building entity to mark as an observer to another entity.
 
 /// An observer component. Mutated subjects will update this component.
 pub trait Observer<T: Send + Sync + 'static>: Component {
-    fn receive_data(&mut self, data: &T, asset_server: &Res<AssetServer>, sender: Entity);
+    /// System params fetched alongside the observer and passed to receive_data.
+    /// Use `()` if the observer needs nothing from the world.
+    type Param: SystemParam + 'static;
+
+    fn receive_data(&mut self, data: &T, param: &mut SystemParamItem<Self::Param>, sender: Entity);
+
+    /// Called when an observed subject is despawned or loses its subject component.
+    fn on_subject_lost(&mut self, _param: &mut SystemParamItem<Self::Param>, _sender: Entity) {}
 }
 
 /// Marks a component as a possible Subject that can give T
@@ -41,11 +92,55 @@ impl<T: Component> Subject<T> for T {
     }
 }
 
-/// List of entities that are observing this entity.
+/// Marks a component as a Subject that computes T on demand instead of storing it.
+/// Linked with set_computed_observer and registered with register_computed_observer.
+pub trait ComputedSubject<T: Send + Sync + 'static>: Component {
+    fn compute_data(&self) -> T;
+}
+
+/// Sorts the entities and removes duplicates, so link lists are stored the same way every time.
+pub(crate) fn sorted_entities(list: impl IntoIterator<Item = Entity>) -> Vec<Entity> {
+    let mut list: Vec<Entity> = list.into_iter().collect();
+    list.sort();
+    list.dedup();
+    list
+}
+
+/// Inserts the entity into a sorted list, returning whether it wasn't there yet.
+pub(crate) fn insert_sorted(list: &mut Vec<Entity>, entity: Entity) -> bool {
+    match list.binary_search(&entity) {
+        Ok(_) => false,
+        Err(index) => {
+            list.insert(index, entity);
+            true
+        }
+    }
+}
+
+/// Removes the entity from a sorted list, returning whether it was there.
+pub(crate) fn remove_sorted(list: &mut Vec<Entity>, entity: Entity) -> bool {
+    match list.binary_search(&entity) {
+        Ok(index) => {
+            list.remove(index);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// List of entities that are observing this entity, sorted so scenes save it the same way
+/// every time.
 #[derive(Reflect, FromReflect, Clone, Component)]
 #[reflect(Component, MapEntities)]
-pub struct ObserverList<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>> {
-    observers: HashSet<Entity>,
+pub struct ObserverList<T: Send + Sync + 'static, S: Component, O: Observer<T>> {
+    observers: Vec<Entity>,
+
+    #[reflect(ignore)]
+    unmapped: Vec<Entity>,
+
+    /// Last value sent to the observers, only kept by observers registered with diffing.
+    #[reflect(ignore)]
+    last_sent: Option<T>,
 
     #[reflect(ignore)]
     phantom_data: PhantomData<T>,
@@ -57,47 +152,187 @@ pub struct ObserverList<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>
     phantom_observer: PhantomData<O>,
 }
 
-impl<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>> Deref for ObserverList<T, S, O> {
-    type Target = HashSet<Entity>;
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Deref for ObserverList<T, S, O> {
+    type Target = [Entity];
     fn deref(&self) -> &Self::Target {
         &self.observers
     }
 }
 
-impl<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>> DerefMut for ObserverList<T, S, O> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.observers
-    }
-}
-
-impl<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>> ObserverList<T, S, O> {
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> ObserverList<T, S, O> {
     pub fn new(list: impl IntoIterator<Item = Entity>) -> Self {
         ObserverList {
-            observers: list.into_iter().collect(),
+            observers: sorted_entities(list),
+            unmapped: Vec::new(),
+            last_sent: None,
             phantom_data: PhantomData,
             phantom_subject: PhantomData,
             phantom_observer: PhantomData,
         }
     }
+
+    /// Adds an observer, returning whether it wasn't in the list yet.
+    pub fn insert(&mut self, observer: Entity) -> bool {
+        insert_sorted(&mut self.observers, observer)
+    }
+
+    /// Removes an observer, returning whether it was in the list.
+    pub fn remove(&mut self, observer: Entity) -> bool {
+        remove_sorted(&mut self.observers, observer)
+    }
 }
-impl<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>> Default for ObserverList<T, S, O> {
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Default for ObserverList<T, S, O> {
     fn default() -> Self {
         ObserverList::new(vec![])
     }
 }
-impl<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>> MapEntities
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> MapEntities for ObserverList<T, S, O> {
+    fn map_entities(&mut self, m: &EntityMap) -> Result<(), MapEntitiesError> {
+        let (mapped, unmapped) = scene::map_links(self.observers.drain(..), m);
+        self.observers = sorted_entities(mapped);
+        self.unmapped = unmapped;
+        Ok(())
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> scene::LinkEntities
     for ObserverList<T, S, O>
 {
-    fn map_entities(&mut self, m: &EntityMap) -> Result<(), MapEntitiesError> {
-        let mut new_set = HashSet::default();
-        for receiver in self.observers.iter() {
-            new_set.insert(m.get(*receiver).unwrap());
+    const LINK: &'static str = "ObserverList";
+
+    fn unmapped(&mut self) -> &mut Vec<Entity> {
+        &mut self.unmapped
+    }
+
+    fn keep_link(&mut self, entity: Entity) {
+        self.insert(entity);
+    }
+}
+
+/// List of entities that this entity is observing, sorted like ObserverList.
+/// Kept in sync with the subjects' ObserverList by the observer commands.
+#[derive(Reflect, FromReflect, Clone, Component)]
+#[reflect(Component, MapEntities)]
+pub struct ObservingList<T: Send + Sync + 'static, S: Component, O: Observer<T>> {
+    subjects: Vec<Entity>,
+
+    #[reflect(ignore)]
+    unmapped: Vec<Entity>,
+
+    #[reflect(ignore)]
+    phantom_data: PhantomData<T>,
+
+    #[reflect(ignore)]
+    phantom_subject: PhantomData<S>,
+
+    #[reflect(ignore)]
+    phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Deref for ObservingList<T, S, O> {
+    type Target = [Entity];
+    fn deref(&self) -> &Self::Target {
+        &self.subjects
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> ObservingList<T, S, O> {
+    pub fn new(list: impl IntoIterator<Item = Entity>) -> Self {
+        ObservingList {
+            subjects: sorted_entities(list),
+            unmapped: Vec::new(),
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
         }
-        self.observers = new_set;
+    }
+}
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Default for ObservingList<T, S, O> {
+    fn default() -> Self {
+        ObservingList::new(vec![])
+    }
+}
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> MapEntities
+    for ObservingList<T, S, O>
+{
+    fn map_entities(&mut self, m: &EntityMap) -> Result<(), MapEntitiesError> {
+        let (mapped, unmapped) = scene::map_links(self.subjects.drain(..), m);
+        self.subjects = sorted_entities(mapped);
+        self.unmapped = unmapped;
         Ok(())
     }
 }
 
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> scene::LinkEntities
+    for ObservingList<T, S, O>
+{
+    const LINK: &'static str = "ObservingList";
+
+    fn unmapped(&mut self) -> &mut Vec<Entity> {
+        &mut self.unmapped
+    }
+
+    fn keep_link(&mut self, entity: Entity) {
+        insert_sorted(&mut self.subjects, entity);
+    }
+}
+
+/// Removes the given subjects from the observer's ObservingList, dropping the list when empty.
+fn unlink_subjects<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    world: &mut World,
+    observer: Entity,
+    subjects: impl IntoIterator<Item = Entity>,
+) {
+    let Some(mut entity_mut) = world.get_entity_mut(observer) else {
+        return;
+    };
+    let Some(mut observing_list) = entity_mut.get_mut::<ObservingList<T, S, O>>() else {
+        return;
+    };
+    for subject in subjects {
+        remove_sorted(&mut observing_list.subjects, subject);
+    }
+    if observing_list.subjects.is_empty() {
+        entity_mut.remove::<ObservingList<T, S, O>>();
+    }
+}
+
+/// Adds the observer to the subjects' ObserverLists and the subjects to its ObservingList.
+pub(crate) fn link_subjects<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    world: &mut World,
+    observer: Entity,
+    subjects: &[Entity],
+) {
+    registry::warn_link_cycles::<S, O>(world, observer, subjects);
+
+    for &source in subjects.iter() {
+        let Some(mut entity_mut) = world.get_entity_mut(source) else {
+            continue;
+        };
+        match entity_mut.get_mut::<ObserverList<T, S, O>>() {
+            Some(mut observer_list) => {
+                observer_list.insert(observer);
+            }
+            None => {
+                entity_mut.insert(ObserverList::<T, S, O>::new(vec![observer]));
+            }
+        }
+    }
+
+    if let Some(mut entity_mut) = world.get_entity_mut(observer) {
+        match entity_mut.get_mut::<ObservingList<T, S, O>>() {
+            Some(mut observing_list) => {
+                for &subject in subjects.iter() {
+                    insert_sorted(&mut observing_list.subjects, subject);
+                }
+            }
+            None => {
+                entity_mut.insert(ObservingList::<T, S, O>::new(subjects.to_vec()));
+            }
+        }
+    }
+}
+
 struct ObserverBuildCommand<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>> {
     pub observer: Entity,
     pub subjects: Vec<Entity>,
@@ -110,34 +345,101 @@ impl<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>> Command
     for ObserverBuildCommand<T, S, O>
 {
     fn write(self, world: &mut World) {
-        for &source in self.subjects.iter() {
-            match world.entity(source).contains::<ObserverList<T, S, O>>() {
-                false => {
-                    world
-                        .entity_mut(source)
-                        .insert(ObserverList::<T, S, O>::new(vec![self.observer]));
-                }
-                true => {
-                    let mut entity_mut = world.entity_mut(source);
-                    let mut observer_list = entity_mut.get_mut::<ObserverList<T, S, O>>().unwrap();
-                    observer_list.observers.insert(self.observer);
+        link_subjects::<T, S, O>(world, self.observer, &self.subjects);
+
+        let mut subjects = self.subjects;
+        if let Some(ordering) = world.get_resource::<ObserverOrdering>() {
+            ordering.sort_by_entity(&mut subjects, |subject| *subject);
+        }
+
+        let mut system_state: SystemState<(
+            StaticSystemParam<O::Param>,
+            Query<&mut O>,
+            Query<(Entity, &S)>,
+        )> = SystemState::new(world);
+
+        {
+            let (mut param, mut observer_query, subject_query) = system_state.get_mut(world);
+
+            if let Ok(mut observer) = observer_query.get_mut(self.observer) {
+                for &source in subjects.iter() {
+                    if let Ok((subject, subject_comp)) = subject_query.get(source) {
+                        let data = subject_comp.give_data();
+                        observer.receive_data(data, &mut param, subject)
+                    }
                 }
             }
         }
 
-        let mut system_state: SystemState<(Res<AssetServer>, Query<&mut O>, Query<(Entity, &S)>)> =
-            SystemState::new(world);
+        system_state.apply(world);
+    }
+}
 
-        let (asset_server, mut observer_query, subject_query) = system_state.get_mut(world);
+/// Links the observer to the subjects with an initial sync, such as when restoring scene links.
+fn restore_observer<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+    world: &mut World,
+    observer: Entity,
+    subjects: Vec<Entity>,
+) {
+    ObserverBuildCommand::<T, S, O> {
+        observer,
+        subjects,
+        phantom_data: PhantomData,
+        phantom_subject: PhantomData,
+        phantom_observer: PhantomData,
+    }
+    .write(world)
+}
 
-        if let Ok(mut observer) = observer_query.get_mut(self.observer) {
-            for &source in self.subjects.iter() {
-                if let Ok((subject, subject_comp)) = subject_query.get(source) {
-                    let data = subject_comp.give_data();
-                    observer.receive_data(data, &asset_server, subject)
-                }
+struct ObserverRemoveCommand<T: Send + Sync + 'static, S: Component, O: Observer<T>> {
+    pub observer: Entity,
+    pub subjects: Vec<Entity>,
+    phantom_data: PhantomData<T>,
+    phantom_subject: PhantomData<S>,
+    phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Command
+    for ObserverRemoveCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        for &source in self.subjects.iter() {
+            let Some(mut entity_mut) = world.get_entity_mut(source) else {
+                continue;
+            };
+            let Some(mut observer_list) = entity_mut.get_mut::<ObserverList<T, S, O>>() else {
+                continue;
+            };
+            observer_list.remove(self.observer);
+            if observer_list.observers.is_empty() {
+                entity_mut.remove::<ObserverList<T, S, O>>();
             }
         }
+
+        unlink_subjects::<T, S, O>(world, self.observer, self.subjects);
+    }
+}
+
+struct ObserverClearCommand<T: Send + Sync + 'static, S: Component, O: Observer<T>> {
+    pub subject: Entity,
+    phantom_data: PhantomData<T>,
+    phantom_subject: PhantomData<S>,
+    phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Command
+    for ObserverClearCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        let Some(mut entity_mut) = world.get_entity_mut(self.subject) else {
+            return;
+        };
+        let Some(observer_list) = entity_mut.take::<ObserverList<T, S, O>>() else {
+            return;
+        };
+        for &observer in observer_list.observers.iter() {
+            unlink_subjects::<T, S, O>(world, observer, [self.subject]);
+        }
     }
 }
 
@@ -147,6 +449,96 @@ pub trait ObserverBuildCommandExt {
         &mut self,
         source: Vec<Entity>,
     ) -> &mut Self;
+
+    /// Stops the component O on this entity from observing component S on the source entities.
+    /// Subjects left without observers lose their ObserverList.
+    fn remove_observer<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
+
+    /// Removes every O observer from component S on this entity.
+    fn clear_observers<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Sets the component O on this entity to observe the value computed by `map`
+    /// from component S on the source entities.
+    fn set_observer_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+        map: impl Fn(&S) -> T + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Stops the component O on this entity from observing component S on the source entities
+    /// through a mapping closure.
+    fn remove_observer_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
+
+    /// Removes every O observer mapping component S on this entity.
+    fn clear_observers_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Sets the aggregate observer O on this entity to observe component S on the source entities,
+    /// after any subjects it already aggregates.
+    fn set_aggregate_observer<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
+
+    /// Stops the aggregate observer O on this entity from observing component S on the source
+    /// entities. The observer is updated with the subjects it has left.
+    fn remove_aggregate_observer<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
+
+    /// Removes every O aggregate observer from component S on this entity.
+    fn clear_aggregate_observers<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Sets the component O on this entity to observe the resource R.
+    /// Observers of resources receive RESOURCE_SUBJECT as the sender.
+    fn set_resource_observer<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Stops the component O on this entity from observing the resource R.
+    fn remove_resource_observer<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Links component O on this entity and component S on the source entities both ways.
+    /// O is synced from S first, then an edit to either side is sent to the other.
+    fn set_two_way_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
+
+    /// Removes the two-way links between component O on this entity and S on the source entities.
+    fn remove_two_way_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
+
+    /// Sets the component O on this entity to observe the value computed by S on the source entities.
+    /// Computed links share the ObserverList of S, so remove_observer and clear_observers apply.
+    fn set_computed_observer<T: Send + Sync + 'static, S: ComputedSubject<T>, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
 }
 
 impl<'w, 's, 'a> ObserverBuildCommandExt for EntityCommands<'w, 's, 'a> {
@@ -166,187 +558,3152 @@ impl<'w, 's, 'a> ObserverBuildCommandExt for EntityCommands<'w, 's, 'a> {
 
         self
     }
-}
 
-impl<'w> ObserverBuildCommandExt for EntityMut<'w> {
-    fn set_observer<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+    fn remove_observer<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
         &mut self,
         sources: Vec<Entity>,
     ) -> &mut Self {
         let id = self.id();
-        unsafe {
-            let world = self.world_mut();
-            ObserverBuildCommand::<T, S, O> {
-                observer: id,
-                subjects: sources,
-                phantom_data: PhantomData,
-                phantom_subject: PhantomData,
-                phantom_observer: PhantomData,
-            }
-            .write(world)
-        }
+
+        self.commands().add(ObserverRemoveCommand::<T, S, O> {
+            observer: id,
+            subjects: sources,
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        });
 
         self
     }
-}
 
-/// Receives subject events from subjects and updates any observer component in ObserverList.
-fn recieve_subject_event<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
-    asset_server: Res<AssetServer>,
-    mut observer_query: Query<&mut O>,
-    mut observer_list_query: Query<(Entity, &S, &mut ObserverList<T, S, O>), Changed<S>>,
-) {
-    for (subject, subject_comp, mut observer_list) in observer_list_query.iter_mut() {
-        let data = Subject::<T>::give_data(subject_comp);
-        let mut remove_list = Vec::<Entity>::new();
-        for &observer in observer_list.observers.iter() {
-            match observer_query.get_mut(observer) {
-                Ok(mut observer) => {
-                    observer.receive_data(data, &asset_server, subject);
-                }
-                Err(QueryEntityError::NoSuchEntity { .. }) => remove_list.push(observer),
-                _ => (),
-            }
-        }
+    fn clear_observers<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
 
-        observer_list.observers.retain(|x| !remove_list.contains(x));
-    }
-}
+        self.commands().add(ObserverClearCommand::<T, S, O> {
+            subject: id,
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        });
 
-pub trait ObserverRegisterExt {
-    /// Register a type as capable of observing.
-    fn register_observer<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
-        &mut self,
-    ) -> &mut Self;
-}
+        self
+    }
 
-impl ObserverRegisterExt for App {
-    fn register_observer<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+    fn set_observer_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
         &mut self,
+        sources: Vec<Entity>,
+        map: impl Fn(&S) -> T + Send + Sync + 'static,
     ) -> &mut Self {
-        self.register_type::<ObserverList<T, S, O>>().add_system(
-            recieve_subject_event::<T, S, O>.in_base_set(bevy::prelude::CoreSet::PostUpdate),
-        );
+        let id = self.id();
+
+        self.commands()
+            .add(mapped::MappedObserverBuildCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                map: Arc::new(map),
+                phantom_observer: PhantomData,
+            });
+
         self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use bevy::prelude::*;
+    fn remove_observer_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
 
-    use crate::{Observer, ObserverBuildCommandExt, ObserverRegisterExt, Subject};
+        self.commands()
+            .add(mapped::MappedObserverRemoveCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
 
-    #[derive(Component)]
-    struct TestSubject {
-        a: String,
+        self
+    }
+
+    fn clear_observers_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands()
+            .add(mapped::MappedObserverClearCommand::<T, S, O> {
+                subject: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
+
+        self
+    }
+    fn set_computed_observer<T: Send + Sync + 'static, S: ComputedSubject<T>, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands()
+            .add(computed::ComputedObserverBuildCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
+
+        self
+    }
+
+    fn set_aggregate_observer<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands()
+            .add(aggregate::AggregateObserverBuildCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
+
+        self
+    }
+
+    fn remove_aggregate_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        O: AggregateObserver<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands()
+            .add(aggregate::AggregateObserverRemoveCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
+
+        self
+    }
+
+    fn clear_aggregate_observers<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        O: AggregateObserver<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands()
+            .add(aggregate::AggregateObserverClearCommand::<T, S, O> {
+                subject: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
+
+        self
+    }
+
+    fn set_resource_observer<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands()
+            .add(resource::ResourceObserverBuildCommand::<T, R, O> {
+                observer: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
+
+        self
+    }
+
+    fn remove_resource_observer<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands()
+            .add(resource::ResourceObserverRemoveCommand::<T, R, O> {
+                observer: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
+
+        self
+    }
+    fn set_two_way_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands().add(two_way::TwoWayBuildCommand::<T, S, O> {
+            observer: id,
+            subjects: sources,
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        });
+
+        self
+    }
+
+    fn remove_two_way_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+
+        self.commands()
+            .add(two_way::TwoWayRemoveCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            });
+
+        self
+    }
+}
+
+impl<'w> ObserverBuildCommandExt for EntityMut<'w> {
+    fn set_observer<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            ObserverBuildCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn remove_observer<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            ObserverRemoveCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn clear_observers<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            ObserverClearCommand::<T, S, O> {
+                subject: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn set_observer_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+        map: impl Fn(&S) -> T + Send + Sync + 'static,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            mapped::MappedObserverBuildCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                map: Arc::new(map),
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn remove_observer_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            mapped::MappedObserverRemoveCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn clear_observers_with<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            mapped::MappedObserverClearCommand::<T, S, O> {
+                subject: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+    fn set_computed_observer<T: Send + Sync + 'static, S: ComputedSubject<T>, O: Observer<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            computed::ComputedObserverBuildCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn set_aggregate_observer<T: Send + Sync + 'static, S: Subject<T>, O: AggregateObserver<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            aggregate::AggregateObserverBuildCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn remove_aggregate_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        O: AggregateObserver<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            aggregate::AggregateObserverRemoveCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn clear_aggregate_observers<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        O: AggregateObserver<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            aggregate::AggregateObserverClearCommand::<T, S, O> {
+                subject: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn set_resource_observer<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            resource::ResourceObserverBuildCommand::<T, R, O> {
+                observer: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn remove_resource_observer<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            resource::ResourceObserverRemoveCommand::<T, R, O> {
+                observer: id,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+    fn set_two_way_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            two_way::TwoWayBuildCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+
+    fn remove_two_way_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        let id = self.id();
+        unsafe {
+            let world = self.world_mut();
+            two_way::TwoWayRemoveCommand::<T, S, O> {
+                observer: id,
+                subjects: sources,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        }
+        self.update_location();
+
+        self
+    }
+}
+
+/// Receives subject events from subjects and updates any observer component in ObserverList.
+fn recieve_subject_event<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+    mut param: StaticSystemParam<O::Param>,
+    mut observer_query: Query<&mut O>,
+    mut observer_list_query: Query<(Entity, &S, &mut ObserverList<T, S, O>), Changed<S>>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let mut changed: Vec<_> = observer_list_query.iter_mut().collect();
+    ordering.sort_by_entity(&mut changed, |(subject, ..)| *subject);
+
+    for (subject, subject_comp, mut observer_list) in changed {
+        let data = Subject::<T>::give_data(subject_comp);
+        send_to_observers(
+            data,
+            subject,
+            &mut observer_list,
+            &mut observer_query,
+            &mut param,
+            *ordering,
+        );
+    }
+}
+
+/// Same as recieve_subject_event, but skips subjects whose data equals the last value sent.
+/// Every observer of a subject receives the same value, so the cache is kept per subject.
+fn recieve_diffed_subject_event<
+    T: PartialEq + Clone + Send + Sync + 'static,
+    S: Subject<T>,
+    O: Observer<T>,
+>(
+    mut param: StaticSystemParam<O::Param>,
+    mut observer_query: Query<&mut O>,
+    mut observer_list_query: Query<(Entity, &S, &mut ObserverList<T, S, O>), Changed<S>>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let mut changed: Vec<_> = observer_list_query.iter_mut().collect();
+    ordering.sort_by_entity(&mut changed, |(subject, ..)| *subject);
+
+    for (subject, subject_comp, mut observer_list) in changed {
+        let data = Subject::<T>::give_data(subject_comp);
+        if observer_list.last_sent.as_ref() == Some(data) {
+            continue;
+        }
+        observer_list.last_sent = Some(data.clone());
+        send_to_observers(
+            data,
+            subject,
+            &mut observer_list,
+            &mut observer_query,
+            &mut param,
+            *ordering,
+        );
+    }
+}
+
+/// Gives data to every observer in the list, pruning observers that no longer exist.
+pub(crate) fn send_to_observers<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    data: &T,
+    subject: Entity,
+    observer_list: &mut ObserverList<T, S, O>,
+    observer_query: &mut Query<&mut O>,
+    param: &mut SystemParamItem<O::Param>,
+    ordering: ObserverOrdering,
+) {
+    let mut observers = observer_list.observers.clone();
+    ordering.sort_by_entity(&mut observers, |observer| *observer);
+
+    let mut remove_list = Vec::<Entity>::new();
+    for observer in observers {
+        match observer_query.get_mut(observer) {
+            Ok(mut observer) => {
+                observer.receive_data(data, param, subject);
+            }
+            Err(QueryEntityError::NoSuchEntity { .. }) => remove_list.push(observer),
+            _ => (),
+        }
+    }
+
+    observer_list.observers.retain(|x| !remove_list.contains(x));
+}
+
+/// Sent whenever a link is dropped because its observer lost the O component or was despawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObserverLinkDropped {
+    pub subject: Entity,
+    pub observer: Entity,
+}
+
+/// Removes observers that lost their O component from every ObserverList.
+fn cleanup_removed_observers<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<O>,
+    mut observer_list_query: Query<(Entity, &mut ObserverList<T, S, O>)>,
+    observing_list_query: Query<(), With<ObservingList<T, S, O>>>,
+    mut dropped_events: EventWriter<ObserverLinkDropped>,
+) {
+    let removed: HashSet<Entity> = removed.iter().collect();
+    if removed.is_empty() {
+        return;
+    }
+
+    for (subject, mut observer_list) in observer_list_query.iter_mut() {
+        if !observer_list
+            .iter()
+            .any(|observer| removed.contains(observer))
+        {
+            continue;
+        }
+        for &observer in removed.iter() {
+            if observer_list.remove(observer) {
+                debug!(
+                    "Dropped stale observer link {:?} -> {:?}",
+                    subject, observer
+                );
+                dropped_events.send(ObserverLinkDropped { subject, observer });
+            }
+        }
+        if observer_list.observers.is_empty() {
+            commands.entity(subject).remove::<ObserverList<T, S, O>>();
+        }
+    }
+
+    for &observer in removed.iter() {
+        if observing_list_query.contains(observer) {
+            commands.entity(observer).remove::<ObservingList<T, S, O>>();
+        }
+    }
+}
+
+/// Tells observers about subjects that were despawned or lost their S component.
+fn notify_lost_subjects<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    mut commands: Commands,
+    mut param: StaticSystemParam<O::Param>,
+    mut removed: RemovedComponents<S>,
+    mut observer_query: Query<(Entity, &mut O, &mut ObservingList<T, S, O>)>,
+    observer_list_query: Query<(), With<ObserverList<T, S, O>>>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let removed: HashSet<Entity> = removed.iter().collect();
+    if removed.is_empty() {
+        return;
+    }
+    let mut removed_in_order: Vec<Entity> = removed.iter().copied().collect();
+    ordering.sort_by_entity(&mut removed_in_order, |subject| *subject);
+
+    for (observer, mut observer_comp, mut observing_list) in observer_query.iter_mut() {
+        if !observing_list
+            .iter()
+            .any(|subject| removed.contains(subject))
+        {
+            continue;
+        }
+        for &subject in removed_in_order.iter() {
+            if remove_sorted(&mut observing_list.subjects, subject) {
+                observer_comp.on_subject_lost(&mut param, subject);
+            }
+        }
+        if observing_list.subjects.is_empty() {
+            commands.entity(observer).remove::<ObservingList<T, S, O>>();
+        }
+    }
+
+    for &subject in removed.iter() {
+        if observer_list_query.contains(subject) {
+            commands.entity(subject).remove::<ObserverList<T, S, O>>();
+        }
+    }
+}
+
+/// How observers and subjects are ordered when several of them are notified at once.
+/// Insert as a resource before registering observers to change it.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObserverOrdering {
+    /// Follow the internal list and query order, which can vary between runs and platforms.
+    #[default]
+    Unordered,
+    /// Notify observers and process subjects sorted by Entity, so that when an observer has
+    /// several subjects, the one with the greatest Entity is written last.
+    SortedByEntity,
+}
+
+impl ObserverOrdering {
+    /// Sorts the items by the entity `key` returns if the ordering asks for it.
+    pub fn sort_by_entity<I>(self, items: &mut [I], key: impl FnMut(&I) -> Entity) {
+        if self == ObserverOrdering::SortedByEntity {
+            items.sort_unstable_by_key(key);
+        }
+    }
+}
+
+/// System sets containing every system added by the observer registrations.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum ObserverSet {
+    /// Drops stale links and notifies observers of lost subjects. Runs before Propagate.
+    Cleanup,
+    /// Sends data from changed subjects to their observers.
+    Propagate,
+}
+
+/// Orders propagation along observer chains such as Stats -> Health -> HealthBar.
+/// Every propagation system writing component C is in the set for C, and every propagation
+/// system reading C runs after it, so a whole chain settles within one frame.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+struct ObserverChainSet {
+    component: TypeId,
+    name: &'static str,
+}
+
+impl ObserverChainSet {
+    fn of<C: 'static>() -> Self {
+        ObserverChainSet {
+            component: TypeId::of::<C>(),
+            name: type_name::<C>(),
+        }
+    }
+}
+
+/// Puts a propagation system reading S and writing O into its place in the observer chain.
+/// S and O are components or resources.
+/// Links closing a cycle between types are left unordered, so the schedule can still be built.
+fn in_observer_chain<S: 'static, O: 'static>(
+    config: SystemConfig,
+    closes_cycle: bool,
+) -> SystemConfig {
+    let config = config
+        .in_set(ObserverSet::Propagate)
+        .in_set(ObserverChainSet::of::<O>());
+    match closes_cycle {
+        true => config,
+        false => config.after(ObserverChainSet::of::<S>()),
+    }
+}
+
+/// Adds the triple to the app's ObserverRegistry, warning if it closes a cycle between types.
+/// Returns true if it does.
+fn register_link(app: &mut App, link: RegisteredLink) -> bool {
+    app.init_resource::<ObserverRegistry>();
+    match app.world.resource_mut::<ObserverRegistry>().register(link) {
+        Ok(()) => false,
+        Err(cycle) => {
+            warn!(
+                "Registered observers form a cycle, changes along it take a frame per hop: {}",
+                cycle.join(" -> ")
+            );
+            true
+        }
+    }
+}
+
+fn observers_in_list<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    world: &World,
+    subject: Entity,
+) -> Vec<Entity> {
+    world
+        .get::<ObserverList<T, S, O>>(subject)
+        .map(|list| list.observers.iter().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Registers the link types of a (T, S, O) triple and adds its systems,
+/// with `place` deciding the schedule or base set they run in.
+fn add_observer_systems<T: Send + Sync + 'static, S: Component, O: Observer<T>, M>(
+    app: &mut App,
+    propagate: impl IntoSystemConfig<M>,
+    place: impl Fn(SystemConfig) -> SystemAppConfig,
+    restore: fn(&mut World, Entity, Vec<Entity>),
+) {
+    let closes_cycle = register_link(
+        app,
+        RegisteredLink::new::<T, S, O>(observers_in_list::<T, S, O>),
+    );
+    scene::register_observer_scene_link::<T, S, O>(app, restore);
+
+    app.init_resource::<ObserverOrdering>()
+        .register_type::<ObserverList<T, S, O>>()
+        .register_type::<ObservingList<T, S, O>>()
+        .add_event::<ObserverLinkDropped>()
+        .add_system(place(
+            cleanup_removed_observers::<T, S, O>
+                .in_set(ObserverSet::Cleanup)
+                .before(ObserverSet::Propagate),
+        ))
+        .add_system(place(
+            notify_lost_subjects::<T, S, O>
+                .in_set(ObserverSet::Cleanup)
+                .before(ObserverSet::Propagate),
+        ))
+        .add_system(place(in_observer_chain::<S, O>(
+            propagate.into_config(),
+            closes_cycle,
+        )));
+    scene::add_unmapped_link_policy::<ObserverList<T, S, O>>(app, &place);
+    scene::add_unmapped_link_policy::<ObservingList<T, S, O>>(app, &place);
+}
+
+fn in_post_update(config: SystemConfig) -> SystemAppConfig {
+    config.in_base_set(CoreSet::PostUpdate).into_app_config()
+}
+
+pub trait ObserverRegisterExt {
+    /// Register a type as capable of observing.
+    /// Propagation runs in ObserverSet::Propagate during CoreSet::PostUpdate.
+    fn register_observer<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Same as register_observer, but propagation runs in the given schedule.
+    /// Use CoreSchedule::Main to propagate during CoreSet::Update.
+    fn register_observer_in_schedule<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+        &mut self,
+        schedule: impl ScheduleLabel + Clone,
+    ) -> &mut Self;
+
+    /// Same as register_observer, but observers are only updated when the data given by S
+    /// differs from the last value sent, not on every change to S.
+    fn register_diffed_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T>,
+        O: Observer<T>,
+    >(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Register a type as capable of observing values mapped from S with set_observer_with.
+    /// These links hold closures and are not saved in scenes.
+    fn register_mapped_observer<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Register a type as capable of observing the resource R with set_resource_observer.
+    fn register_resource_observer<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Register a resource R as capable of observing component S with set_resource_as_observer.
+    fn register_resource_as_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        R: ResourceObserver<T>,
+    >(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Register a type as capable of aggregating S from many subjects with set_aggregate_observer.
+    fn register_aggregate_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        O: AggregateObserver<T>,
+    >(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Register S and O as capable of being linked both ways with set_two_way_observer.
+    /// Edits to O reach S in the same frame, and the other observers of S the frame after.
+    /// S and O must be different components.
+    fn register_two_way_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    >(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Sends a SubjectChanged<T, S> event whenever S changes on any entity,
+    /// in ObserverSet::Propagate alongside the observer updates.
+    fn register_subject_events<T: Clone + Send + Sync + 'static, S: Subject<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Register a type as capable of observing a ComputedSubject.
+    fn register_computed_observer<T: Send + Sync + 'static, S: ComputedSubject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Same as register_computed_observer, but observers are only updated when the computed
+    /// value differs from the last value sent.
+    fn register_diffed_computed_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: ComputedSubject<T>,
+        O: Observer<T>,
+    >(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Adds the system applying ReflectBindings, in ObserverSet::Propagate during
+    /// CoreSet::PostUpdate. Components named by bindings must be registered separately.
+    fn register_reflect_bindings(&mut self) -> &mut Self;
+
+    /// Adds the BindingSet asset, loaded from `.bindings.ron` files, and the system applying it.
+    /// Requires the AssetPlugin.
+    fn register_binding_sets(&mut self) -> &mut Self;
+
+    /// Adds the system writing TextFormats into their Text, after their observers are updated.
+    #[cfg(feature = "bevy_ui")]
+    fn register_text_format(&mut self) -> &mut Self;
+
+    /// Adds the system writing StyleRatios into their Style, after their observers are updated.
+    #[cfg(feature = "bevy_ui")]
+    fn register_style_ratio(&mut self) -> &mut Self;
+
+    /// Adds the system moving the Transform of each Follow, after their observers are updated.
+    /// It isn't ordered before observers of Transform, since Follow usually observes Transform
+    /// itself, so those see the move a frame later.
+    #[cfg(feature = "transform")]
+    fn register_follow(&mut self) -> &mut Self;
+}
+
+impl ObserverRegisterExt for App {
+    fn register_observer<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        add_observer_systems::<T, S, O, _>(
+            self,
+            recieve_subject_event::<T, S, O>,
+            in_post_update,
+            restore_observer::<T, S, O>,
+        );
+        self
+    }
+
+    fn register_observer_in_schedule<T: Send + Sync + 'static, S: Subject<T>, O: Observer<T>>(
+        &mut self,
+        schedule: impl ScheduleLabel + Clone,
+    ) -> &mut Self {
+        add_observer_systems::<T, S, O, _>(
+            self,
+            recieve_subject_event::<T, S, O>,
+            |config| config.in_schedule(schedule.clone()),
+            restore_observer::<T, S, O>,
+        );
+        self
+    }
+
+    fn register_diffed_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T>,
+        O: Observer<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        add_observer_systems::<T, S, O, _>(
+            self,
+            recieve_diffed_subject_event::<T, S, O>,
+            in_post_update,
+            restore_observer::<T, S, O>,
+        );
+        self
+    }
+
+    fn register_mapped_observer<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+        &mut self,
+    ) -> &mut Self {
+        let closes_cycle = register_link(
+            self,
+            RegisteredLink::new::<T, S, O>(mapped::observers_in_mapped_list::<T, S, O>),
+        );
+        self.init_resource::<ObserverOrdering>()
+            .add_event::<ObserverLinkDropped>()
+            .add_system(in_post_update(
+                mapped::cleanup_removed_mapped_observers::<T, S, O>
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            ))
+            .add_system(in_post_update(
+                mapped::notify_lost_mapped_subjects::<T, S, O>
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            ))
+            .add_system(in_post_update(in_observer_chain::<S, O>(
+                mapped::recieve_mapped_subject_event::<T, S, O>.into_config(),
+                closes_cycle,
+            )));
+        self
+    }
+
+    fn register_resource_observer<
+        T: Send + Sync + 'static,
+        R: ResourceSubject<T>,
+        O: Observer<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        let closes_cycle = register_link(self, RegisteredLink::new::<T, R, O>(|_, _| Vec::new()));
+        scene::register_resource_scene_link::<T, R, O>(self);
+        self.init_resource::<ObserverOrdering>()
+            .register_type::<ObservingResource<T, R, O>>()
+            .add_system(in_post_update(in_observer_chain::<R, O>(
+                resource::recieve_resource_event::<T, R, O>.into_config(),
+                closes_cycle,
+            )));
+        self
+    }
+
+    fn register_computed_observer<
+        T: Send + Sync + 'static,
+        S: ComputedSubject<T>,
+        O: Observer<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        add_observer_systems::<T, S, O, _>(
+            self,
+            computed::recieve_computed_subject_event::<T, S, O>,
+            in_post_update,
+            computed::restore_computed_observer::<T, S, O>,
+        );
+        self
+    }
+
+    fn register_diffed_computed_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: ComputedSubject<T>,
+        O: Observer<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        add_observer_systems::<T, S, O, _>(
+            self,
+            computed::recieve_diffed_computed_subject_event::<T, S, O>,
+            in_post_update,
+            computed::restore_computed_observer::<T, S, O>,
+        );
+        self
+    }
+
+    fn register_resource_as_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        R: ResourceObserver<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        let closes_cycle = register_link(self, RegisteredLink::new::<T, S, R>(|_, _| Vec::new()));
+        scene::register_resource_as_observer_scene_link::<T, S, R>(self);
+        self.init_resource::<ObserverOrdering>()
+            .register_type::<ObservedByResource<T, S, R>>()
+            .add_system(in_post_update(in_observer_chain::<S, R>(
+                resource::recieve_subject_event_for_resource::<T, S, R>.into_config(),
+                closes_cycle,
+            )));
+        self
+    }
+
+    fn register_aggregate_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        O: AggregateObserver<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        let closes_cycle = register_link(
+            self,
+            RegisteredLink::new::<T, S, O>(|world, subject| {
+                world
+                    .get::<AggregatedBy<T, S, O>>(subject)
+                    .map(|list| list.iter().copied().collect())
+                    .unwrap_or_default()
+            }),
+        );
+        scene::register_aggregate_scene_link::<T, S, O>(self);
+        self.init_resource::<ObserverOrdering>()
+            .register_type::<AggregateSubjects<T, S, O>>()
+            .register_type::<AggregatedBy<T, S, O>>()
+            .add_event::<ObserverLinkDropped>()
+            .add_system(in_post_update(
+                aggregate::cleanup_removed_aggregate_observers::<T, S, O>
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            ))
+            .add_system(in_post_update(
+                aggregate::prune_lost_aggregate_subjects::<T, S, O>
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            ))
+            .add_system(in_post_update(in_observer_chain::<S, O>(
+                aggregate::recieve_aggregate_subject_event::<T, S, O>.into_config(),
+                closes_cycle,
+            )));
+        scene::add_unmapped_link_policy::<AggregateSubjects<T, S, O>>(self, in_post_update);
+        scene::add_unmapped_link_policy::<AggregatedBy<T, S, O>>(self, in_post_update);
+        self
+    }
+
+    fn register_two_way_observer<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    >(
+        &mut self,
+    ) -> &mut Self {
+        assert_ne!(
+            TypeId::of::<S>(),
+            TypeId::of::<O>(),
+            "Two-way links need different components on each side"
+        );
+
+        // Only the link from S to O is registered, since the link back always closes a cycle.
+        let closes_cycle = register_link(
+            self,
+            RegisteredLink::new::<T, S, O>(observers_in_list::<T, S, O>),
+        );
+        scene::register_two_way_scene_link::<T, S, O>(self);
+
+        self.init_resource::<ObserverOrdering>()
+            .add_event::<ObserverLinkDropped>()
+            .add_system(in_post_update(
+                cleanup_removed_observers::<T, S, O>
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            ))
+            .add_system(in_post_update(
+                cleanup_removed_observers::<T, O, S>
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            ))
+            .add_system(in_post_update(
+                notify_lost_subjects::<T, S, O>
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            ))
+            .add_system(in_post_update(in_observer_chain::<S, O>(
+                two_way::recieve_two_way_event::<T, S, O>.into_config(),
+                closes_cycle,
+            )))
+            .add_system(in_post_update(
+                two_way::recieve_two_way_event::<T, O, S>
+                    .in_set(ObserverSet::Propagate)
+                    .after(ObserverChainSet::of::<O>()),
+            ))
+    }
+
+    fn register_subject_events<T: Clone + Send + Sync + 'static, S: Subject<T>>(
+        &mut self,
+    ) -> &mut Self {
+        self.init_resource::<ObserverOrdering>()
+            .add_event::<SubjectChanged<T, S>>()
+            .add_system(in_post_update(
+                events::send_subject_events::<T, S>
+                    .in_set(ObserverSet::Propagate)
+                    .after(ObserverChainSet::of::<S>()),
+            ));
+        self
+    }
+
+    fn register_reflect_bindings(&mut self) -> &mut Self {
+        scene::add_unmapped_link_policy::<ReflectBindings>(self, in_post_update);
+        self.register_type::<Entity>()
+            .register_type::<String>()
+            .register_type::<ReflectBinding>()
+            .register_type::<Vec<ReflectBinding>>()
+            .register_type::<ReflectBindings>()
+            .add_system(
+                binding::apply_reflect_bindings
+                    .in_base_set(CoreSet::PostUpdate)
+                    .in_set(ObserverSet::Propagate),
+            )
+    }
+
+    fn register_binding_sets(&mut self) -> &mut Self {
+        self.add_asset::<BindingSet>()
+            .init_asset_loader::<binding_set::BindingSetLoader>()
+            .init_resource::<binding_set::AppliedBindingSets>()
+            .add_system(
+                binding_set::apply_binding_sets
+                    .in_base_set(CoreSet::PostUpdate)
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            )
+    }
+
+    #[cfg(feature = "bevy_ui")]
+    fn register_text_format(&mut self) -> &mut Self {
+        self.register_type::<TextFormat>().add_system(
+            impls::write_text_formats
+                .in_base_set(CoreSet::PostUpdate)
+                .in_set(ObserverSet::Propagate)
+                .in_set(ObserverChainSet::of::<bevy::text::Text>())
+                .after(ObserverChainSet::of::<TextFormat>()),
+        )
+    }
+
+    #[cfg(feature = "bevy_ui")]
+    fn register_style_ratio(&mut self) -> &mut Self {
+        self.register_type::<StyleRatio>().add_system(
+            impls::write_style_ratios
+                .in_base_set(CoreSet::PostUpdate)
+                .in_set(ObserverSet::Propagate)
+                .in_set(ObserverChainSet::of::<Style>())
+                .after(ObserverChainSet::of::<StyleRatio>()),
+        )
+    }
+
+    #[cfg(feature = "transform")]
+    fn register_follow(&mut self) -> &mut Self {
+        self.register_type::<Follow>().add_system(
+            impls::write_follows
+                .in_base_set(CoreSet::PostUpdate)
+                .in_set(ObserverSet::Propagate)
+                .after(ObserverChainSet::of::<Follow>()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        ecs::{entity::EntityMap, schedule::ScheduleLabel},
+        prelude::*,
+        scene::{serde::SceneDeserializer, DynamicSceneBuilder},
+    };
+    use serde::de::DeserializeSeed;
+
+    use crate::{
+        AggregateObserver, AggregateSubjects, AggregatedBy, BindingSet, ComponentInfo,
+        ComputedSubject, MappedObserverList, MappedObservingList, Observer,
+        ObserverBuildCommandExt, ObserverLinkDropped, ObserverList, ObserverOrdering,
+        ObserverRegisterExt, ObserverRegistry, ObserverSet, ObservingList, ReflectBinding,
+        ReflectBindings, ResourceObserver, ResourceObserverCommandExt, ResourceSubject, SceneLinks,
+        SceneLinksExt, Subject, SubjectChanged, UnmappedLinkPolicy,
+    };
+
+    #[derive(Component)]
+    struct TestSubject {
+        a: String,
         b: u32,
     }
 
-    impl Subject<String> for TestSubject {
-        fn give_data(&self) -> &String {
-            &self.a
+    impl Subject<String> for TestSubject {
+        fn give_data(&self) -> &String {
+            &self.a
+        }
+    }
+
+    #[derive(Component, Default)]
+    struct TestObserver {
+        a: Option<String>,
+        b: Option<u32>,
+        lost: Option<Entity>,
+    }
+
+    impl Observer<String> for TestObserver {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &String, _param: &mut (), _sender: Entity) {
+            self.a = Some(data.clone());
+        }
+
+        fn on_subject_lost(&mut self, _param: &mut (), sender: Entity) {
+            self.lost = Some(sender);
+        }
+    }
+
+    impl Observer<TestSubject> for TestObserver {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &TestSubject, _param: &mut (), _sender: Entity) {
+            self.a = Some(data.a.clone());
+            self.b = Some(data.b.clone());
+        }
+    }
+
+    fn mutate_data(mut query: Query<&mut TestSubject, Added<TestSubject>>) {
+        for mut giver in query.iter_mut() {
+            giver.a = "Farewell World!".to_string();
+            giver.b = 12;
+        }
+    }
+
+    /// Quick test to see if mutations are picked up.
+    /// Subject and Observer are registered
+    /// mutate_data changes the subject to something else
+    /// Subject and Observer entities are spawned
+    /// After one frame, check and see if the values match.
+    #[test]
+    fn test_data_sync() {
+        let mut app = App::new();
+        app.register_observer::<String, TestSubject, TestObserver>()
+            .add_system(mutate_data);
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Farewell World!".to_string())
+        );
+    }
+
+    /// Same as above, but testing giving the entire component
+    #[test]
+    fn test_self_data_sync() {
+        let mut app = App::new();
+        app.register_observer::<TestSubject, TestSubject, TestObserver>()
+            .add_system(mutate_data);
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<TestSubject, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Farewell World!".to_string())
+        );
+        assert_eq!(app.world.get::<TestObserver>(r).unwrap().b, Some(12));
+    }
+
+    /// Removing the only observer stops updates and drops the subject's ObserverList.
+    #[test]
+    fn test_remove_observer() {
+        let mut app = App::new();
+        app.register_observer::<String, TestSubject, TestObserver>()
+            .add_system(mutate_data);
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .remove_observer::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Hello World!".to_string())
+        );
+        assert!(!app
+            .world
+            .entity(g)
+            .contains::<ObserverList<String, TestSubject, TestObserver>>());
+    }
+
+    /// Clearing a subject removes every observer link at once.
+    #[test]
+    fn test_clear_observers() {
+        let mut app = App::new();
+        app.register_observer::<String, TestSubject, TestObserver>();
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        app.world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g]);
+        app.world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g]);
+
+        app.world
+            .entity_mut(g)
+            .clear_observers::<String, TestSubject, TestObserver>();
+
+        assert!(!app
+            .world
+            .entity(g)
+            .contains::<ObserverList<String, TestSubject, TestObserver>>());
+    }
+
+    /// The observer's ObservingList follows links being added and removed.
+    #[test]
+    fn test_observing_list() {
+        let mut app = App::new();
+        app.register_observer::<String, TestSubject, TestObserver>();
+
+        let g1 = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+        let g2 = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello Again!".to_string(),
+                b: 7,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g1, g2])
+            .id();
+
+        let observing = app
+            .world
+            .get::<ObservingList<String, TestSubject, TestObserver>>(r)
+            .unwrap();
+        assert!(observing.contains(&g1) && observing.contains(&g2));
+
+        app.world
+            .entity_mut(g1)
+            .clear_observers::<String, TestSubject, TestObserver>();
+        let observing = app
+            .world
+            .get::<ObservingList<String, TestSubject, TestObserver>>(r)
+            .unwrap();
+        assert!(!observing.contains(&g1) && observing.contains(&g2));
+
+        app.world
+            .entity_mut(r)
+            .remove_observer::<String, TestSubject, TestObserver>(vec![g2]);
+        assert!(!app
+            .world
+            .entity(r)
+            .contains::<ObservingList<String, TestSubject, TestObserver>>());
+    }
+
+    #[derive(Resource)]
+    struct Greeting(String);
+
+    #[derive(Component, Default)]
+    struct GreetingObserver(String);
+
+    impl Observer<String> for GreetingObserver {
+        type Param = Res<'static, Greeting>;
+
+        fn receive_data(&mut self, data: &String, param: &mut Res<Greeting>, _sender: Entity) {
+            self.0 = format!("{} {}", param.0, data);
+        }
+    }
+
+    /// Observers can pull any resource through their Param.
+    #[test]
+    fn test_observer_param() {
+        let mut app = App::new();
+        app.insert_resource(Greeting("Hi,".to_string()))
+            .register_observer::<String, TestSubject, GreetingObserver>();
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "World".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(GreetingObserver::default())
+            .set_observer::<String, TestSubject, GreetingObserver>(vec![g])
+            .id();
+
+        assert_eq!(app.world.get::<GreetingObserver>(r).unwrap().0, "Hi, World");
+    }
+
+    /// Removing the observer component drops the link on the next update.
+    #[test]
+    fn test_removed_observer_cleanup() {
+        let mut app = App::new();
+        app.register_observer::<String, TestSubject, TestObserver>();
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        app.world.entity_mut(r).remove::<TestObserver>();
+        app.update();
+
+        assert!(!app
+            .world
+            .entity(g)
+            .contains::<ObserverList<String, TestSubject, TestObserver>>());
+        assert!(!app
+            .world
+            .entity(r)
+            .contains::<ObservingList<String, TestSubject, TestObserver>>());
+
+        let events = app.world.resource::<Events<ObserverLinkDropped>>();
+        let dropped: Vec<_> = events.get_reader().iter(events).copied().collect();
+        assert_eq!(
+            dropped,
+            vec![ObserverLinkDropped {
+                subject: g,
+                observer: r
+            }]
+        );
+    }
+
+    /// Despawning a subject calls on_subject_lost on its observers.
+    #[test]
+    fn test_subject_lost() {
+        let mut app = App::new();
+        app.register_observer::<String, TestSubject, TestObserver>();
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        app.update();
+        app.world.despawn(g);
+        app.update();
+
+        assert_eq!(app.world.get::<TestObserver>(r).unwrap().lost, Some(g));
+        assert!(!app
+            .world
+            .entity(r)
+            .contains::<ObservingList<String, TestSubject, TestObserver>>());
+    }
+
+    /// Mapped links compute the observed value from the subject on every change.
+    #[test]
+    fn test_mapped_observer() {
+        let mut app = App::new();
+        app.register_mapped_observer::<String, TestSubject, TestObserver>()
+            .add_system(mutate_data);
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer_with::<String, TestSubject, TestObserver>(vec![g], |subject| {
+                format!("{} x{}", subject.a, subject.b)
+            })
+            .id();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Hello World! x42".to_string())
+        );
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Farewell World! x12".to_string())
+        );
+    }
+
+    /// Mapped links are removed, cleaned up and report lost subjects like plain links.
+    #[test]
+    fn test_mapped_observer_lifecycle() {
+        let mut app = App::new();
+        app.register_mapped_observer::<String, TestSubject, TestObserver>();
+
+        let map = |subject: &TestSubject| subject.a.clone();
+        let spawn_subject = |world: &mut World| {
+            world
+                .spawn(TestSubject {
+                    a: "Hello World!".to_string(),
+                    b: 42,
+                })
+                .id()
+        };
+
+        let g = spawn_subject(&mut app.world);
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer_with::<String, TestSubject, TestObserver>(vec![g], map)
+            .remove_observer_with::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        assert!(!app
+            .world
+            .entity(g)
+            .contains::<MappedObserverList<String, TestSubject, TestObserver>>());
+        assert!(!app
+            .world
+            .entity(r)
+            .contains::<MappedObservingList<String, TestSubject, TestObserver>>());
+
+        app.world
+            .entity_mut(r)
+            .set_observer_with::<String, TestSubject, TestObserver>(vec![g], map);
+        app.world
+            .entity_mut(g)
+            .clear_observers_with::<String, TestSubject, TestObserver>();
+
+        assert!(!app
+            .world
+            .entity(r)
+            .contains::<MappedObservingList<String, TestSubject, TestObserver>>());
+
+        // Losing the observer component drops the link.
+        app.world
+            .entity_mut(r)
+            .set_observer_with::<String, TestSubject, TestObserver>(vec![g], map);
+        app.world.entity_mut(r).remove::<TestObserver>();
+        app.update();
+
+        assert!(!app
+            .world
+            .entity(g)
+            .contains::<MappedObserverList<String, TestSubject, TestObserver>>());
+
+        // Despawning the subject notifies the observer.
+        app.world.entity_mut(r).insert(TestObserver::default());
+        let h = spawn_subject(&mut app.world);
+        app.world
+            .entity_mut(r)
+            .set_observer_with::<String, TestSubject, TestObserver>(vec![h], map);
+        app.update();
+        app.world.despawn(h);
+        app.update();
+
+        assert_eq!(app.world.get::<TestObserver>(r).unwrap().lost, Some(h));
+        assert!(!app
+            .world
+            .entity(r)
+            .contains::<MappedObservingList<String, TestSubject, TestObserver>>());
+    }
+
+    #[derive(Component)]
+    struct TestHealth {
+        hp: u32,
+        max_hp: u32,
+    }
+
+    impl ComputedSubject<f32> for TestHealth {
+        fn compute_data(&self) -> f32 {
+            self.hp as f32 / self.max_hp as f32
+        }
+    }
+
+    #[derive(Component, Default)]
+    struct TestBar(f32);
+
+    impl Observer<f32> for TestBar {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &f32, _param: &mut (), _sender: Entity) {
+            self.0 = *data;
+        }
+    }
+
+    /// Computed subjects give owned values on link and on every change.
+    #[test]
+    fn test_computed_subject() {
+        let mut app = App::new();
+        app.register_computed_observer::<f32, TestHealth, TestBar>();
+
+        let g = app
+            .world
+            .spawn(TestHealth {
+                hp: 50,
+                max_hp: 100,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestBar::default())
+            .set_computed_observer::<f32, TestHealth, TestBar>(vec![g])
+            .id();
+
+        assert_eq!(app.world.get::<TestBar>(r).unwrap().0, 0.5);
+        assert!(app
+            .world
+            .get::<ObserverList<f32, TestHealth, TestBar>>(g)
+            .unwrap()
+            .contains(&r));
+
+        app.world.get_mut::<TestHealth>(g).unwrap().hp = 25;
+        app.update();
+
+        assert_eq!(app.world.get::<TestBar>(r).unwrap().0, 0.25);
+
+        // Computed links are plain ObserverList links.
+        app.world
+            .entity_mut(r)
+            .remove_observer::<f32, TestHealth, TestBar>(vec![g]);
+        assert!(!app
+            .world
+            .entity(g)
+            .contains::<ObserverList<f32, TestHealth, TestBar>>());
+    }
+
+    #[cfg(feature = "derive")]
+    mod derive {
+        use bevy::prelude::*;
+
+        use crate::{ObserverBuildCommandExt, ObserverRegisterExt};
+
+        #[derive(Component, crate::Subject)]
+        struct DerivedSubject {
+            #[subject]
+            name: String,
+            #[subject]
+            level: u32,
+        }
+
+        #[derive(Component, Default, crate::Observer)]
+        struct DerivedObserver {
+            #[observe(String)]
+            name: Option<String>,
+            #[observe(u32)]
+            level: u32,
+        }
+
+        /// Derived impls behave like the hand-written ones.
+        #[test]
+        fn test_derive() {
+            let mut app = App::new();
+            app.register_observer::<String, DerivedSubject, DerivedObserver>()
+                .register_observer::<u32, DerivedSubject, DerivedObserver>();
+
+            let g = app
+                .world
+                .spawn(DerivedSubject {
+                    name: "Hero".to_string(),
+                    level: 3,
+                })
+                .id();
+
+            let r = app
+                .world
+                .spawn(DerivedObserver::default())
+                .set_observer::<String, DerivedSubject, DerivedObserver>(vec![g])
+                .set_observer::<u32, DerivedSubject, DerivedObserver>(vec![g])
+                .id();
+
+            let observer = app.world.get::<DerivedObserver>(r).unwrap();
+            assert_eq!(observer.name, Some("Hero".to_string()));
+            assert_eq!(observer.level, 3);
+        }
+    }
+
+    /// Diffed observers are not touched when the subject changes without changing its data.
+    #[test]
+    fn test_diffed_observer() {
+        let mut app = App::new();
+        app.register_diffed_observer::<String, TestSubject, TestObserver>();
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        app.update();
+        app.world.get_mut::<TestObserver>(r).unwrap().a = None;
+        app.world.get_mut::<TestSubject>(g).unwrap().b = 7;
+        app.update();
+
+        assert_eq!(app.world.get::<TestObserver>(r).unwrap().a, None);
+
+        app.world.get_mut::<TestSubject>(g).unwrap().a = "Farewell World!".to_string();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Farewell World!".to_string())
+        );
+    }
+
+    /// Diffed computed observers skip changes that compute the same value.
+    #[test]
+    fn test_diffed_computed_observer() {
+        let mut app = App::new();
+        app.register_diffed_computed_observer::<f32, TestHealth, TestBar>();
+
+        let g = app
+            .world
+            .spawn(TestHealth {
+                hp: 50,
+                max_hp: 100,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestBar::default())
+            .set_computed_observer::<f32, TestHealth, TestBar>(vec![g])
+            .id();
+
+        app.update();
+        app.world.get_mut::<TestBar>(r).unwrap().0 = 0.0;
+        *app.world.get_mut::<TestHealth>(g).unwrap() = TestHealth { hp: 25, max_hp: 50 };
+        app.update();
+
+        assert_eq!(app.world.get::<TestBar>(r).unwrap().0, 0.0);
+
+        app.world.get_mut::<TestHealth>(g).unwrap().hp = 10;
+        app.update();
+
+        assert_eq!(app.world.get::<TestBar>(r).unwrap().0, 0.2);
+    }
+
+    #[derive(Resource, Default)]
+    struct SeenAfterPropagate(Option<String>);
+
+    fn read_after_propagate(
+        query: Query<&TestObserver, Changed<TestObserver>>,
+        mut seen: ResMut<SeenAfterPropagate>,
+    ) {
+        for observer in query.iter() {
+            seen.0 = observer.a.clone();
+        }
+    }
+
+    /// User systems can order themselves after ObserverSet::Propagate.
+    #[test]
+    fn test_observer_set_ordering() {
+        let mut app = App::new();
+        app.init_resource::<SeenAfterPropagate>()
+            .register_observer::<String, TestSubject, TestObserver>()
+            .add_system(mutate_data)
+            .add_system(
+                read_after_propagate
+                    .after(ObserverSet::Propagate)
+                    .in_base_set(CoreSet::PostUpdate),
+            );
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        app.world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g]);
+
+        app.update();
+
+        assert_eq!(
+            app.world.resource::<SeenAfterPropagate>().0,
+            Some("Farewell World!".to_string())
+        );
+    }
+
+    #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+    struct TestSchedule;
+
+    /// Propagation can be moved into a custom schedule.
+    #[test]
+    fn test_observer_in_schedule() {
+        let mut app = App::new();
+        app.add_schedule(TestSchedule, Schedule::new())
+            .register_observer_in_schedule::<String, TestSubject, TestObserver>(TestSchedule);
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        app.world.get_mut::<TestSubject>(g).unwrap().a = "Farewell World!".to_string();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Hello World!".to_string())
+        );
+
+        app.world.run_schedule(TestSchedule);
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Farewell World!".to_string())
+        );
+    }
+
+    #[derive(Component)]
+    struct TestChainStats(u32);
+
+    #[derive(Component, Default)]
+    struct TestChainHealth(u32);
+
+    #[derive(Component, Default)]
+    struct TestChainBar(u32);
+
+    impl Observer<TestChainStats> for TestChainHealth {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &TestChainStats, _param: &mut (), _sender: Entity) {
+            self.0 = data.0 * 10;
+        }
+    }
+
+    impl Observer<TestChainHealth> for TestChainBar {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &TestChainHealth, _param: &mut (), _sender: Entity) {
+            self.0 = data.0;
+        }
+    }
+
+    /// A chain of observers settles within a single update, whatever the registration order.
+    #[test]
+    fn test_observer_chain() {
+        let mut app = App::new();
+        app.register_observer::<TestChainHealth, TestChainHealth, TestChainBar>()
+            .register_observer::<TestChainStats, TestChainStats, TestChainHealth>();
+
+        let stats = app.world.spawn(TestChainStats(1)).id();
+        let health = app
+            .world
+            .spawn(TestChainHealth::default())
+            .set_observer::<TestChainStats, TestChainStats, TestChainHealth>(vec![stats])
+            .id();
+        let bar = app
+            .world
+            .spawn(TestChainBar::default())
+            .set_observer::<TestChainHealth, TestChainHealth, TestChainBar>(vec![health])
+            .id();
+
+        app.update();
+        app.world.get_mut::<TestChainStats>(stats).unwrap().0 = 5;
+        app.update();
+
+        assert_eq!(app.world.get::<TestChainBar>(bar).unwrap().0, 50);
+    }
+
+    #[derive(Component, Default)]
+    struct TestPing(u32);
+
+    #[derive(Component, Default)]
+    struct TestPong(u32);
+
+    impl Observer<TestPing> for TestPong {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &TestPing, _param: &mut (), _sender: Entity) {
+            self.0 = data.0;
+        }
+    }
+
+    impl Observer<TestPong> for TestPing {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &TestPong, _param: &mut (), _sender: Entity) {
+            self.0 = data.0;
+        }
+    }
+
+    /// Cycles are reported between types and between linked entities,
+    /// and the schedule still builds.
+    #[test]
+    fn test_cycle_detection() {
+        let mut app = App::new();
+        app.register_observer::<TestPing, TestPing, TestPong>()
+            .register_observer::<TestPong, TestPong, TestPing>();
+
+        let ping = app.world.spawn(TestPing::default()).id();
+        let pong = app
+            .world
+            .spawn(TestPong::default())
+            .set_observer::<TestPing, TestPing, TestPong>(vec![ping])
+            .id();
+        app.world
+            .entity_mut(ping)
+            .set_observer::<TestPong, TestPong, TestPing>(vec![pong]);
+
+        app.update();
+
+        let registry = app.world.resource::<ObserverRegistry>();
+        let path = registry
+            .find_link_path(
+                &app.world,
+                (ping, ComponentInfo::of::<TestPing>()),
+                (pong, ComponentInfo::of::<TestPong>()),
+            )
+            .unwrap();
+        assert_eq!(
+            path.iter().map(|(e, _)| *e).collect::<Vec<_>>(),
+            vec![ping, pong]
+        );
+
+        let mut registry = registry.clone();
+        let cycle = registry
+            .register(crate::RegisteredLink::new::<TestPing, TestPing, TestPong>(
+                |_, _| Vec::new(),
+            ))
+            .unwrap_err();
+        assert_eq!(cycle.len(), 3);
+    }
+
+    #[derive(Resource)]
+    struct TestLocale(String);
+
+    impl ResourceSubject<String> for TestLocale {
+        fn give_data(&self) -> &String {
+            &self.0
+        }
+    }
+
+    /// Resource observers sync on link and whenever the resource changes.
+    #[test]
+    fn test_resource_subject() {
+        let mut app = App::new();
+        app.insert_resource(TestLocale("en".to_string()))
+            .register_resource_observer::<String, TestLocale, TestObserver>();
+
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_resource_observer::<String, TestLocale, TestObserver>()
+            .id();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("en".to_string())
+        );
+
+        app.update();
+        app.world.resource_mut::<TestLocale>().0 = "fr".to_string();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("fr".to_string())
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct TestHud {
+        last: Option<String>,
+        sender: Option<Entity>,
+    }
+
+    impl ResourceObserver<String> for TestHud {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &String, _param: &mut (), sender: Entity) {
+            self.last = Some(data.clone());
+            self.sender = Some(sender);
+        }
+    }
+
+    /// Resources can observe components on entities.
+    #[test]
+    fn test_resource_as_observer() {
+        let mut app = App::new();
+        app.init_resource::<TestHud>()
+            .register_resource_as_observer::<String, TestSubject, TestHud>()
+            .add_system(mutate_data);
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        app.world
+            .set_resource_as_observer::<String, TestSubject, TestHud>(vec![g]);
+        assert_eq!(
+            app.world.resource::<TestHud>().last,
+            Some("Hello World!".to_string())
+        );
+
+        app.update();
+
+        let hud = app.world.resource::<TestHud>();
+        assert_eq!(hud.last, Some("Farewell World!".to_string()));
+        assert_eq!(hud.sender, Some(g));
+    }
+
+    /// Subject changes can be read as events without an observer component.
+    #[test]
+    fn test_subject_events() {
+        let mut app = App::new();
+        app.register_subject_events::<String, TestSubject>()
+            .add_system(mutate_data);
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+
+        app.update();
+
+        let events = app
+            .world
+            .resource::<Events<SubjectChanged<String, TestSubject>>>();
+        let changes: Vec<_> = events
+            .get_reader()
+            .iter(events)
+            .map(|e| (e.subject, e.data.clone()))
+            .collect();
+        assert_eq!(changes, vec![(g, "Farewell World!".to_string())]);
+    }
+
+    #[derive(Component)]
+    struct TestGold(u32);
+
+    #[derive(Component, Default)]
+    struct TestPartyGold {
+        total: u32,
+        members: Vec<Entity>,
+    }
+
+    impl AggregateObserver<TestGold> for TestPartyGold {
+        type Param = ();
+
+        fn receive_all<'a>(
+            &mut self,
+            data: impl Iterator<Item = (Entity, &'a TestGold)>,
+            _param: &mut (),
+        ) {
+            self.total = 0;
+            self.members.clear();
+            for (member, gold) in data {
+                self.total += gold.0;
+                self.members.push(member);
+            }
+        }
+    }
+
+    /// Aggregate observers see every subject at once, in link order.
+    #[test]
+    fn test_aggregate_observer() {
+        let mut app = App::new();
+        app.register_aggregate_observer::<TestGold, TestGold, TestPartyGold>();
+
+        let a = app.world.spawn(TestGold(10)).id();
+        let b = app.world.spawn(TestGold(5)).id();
+
+        let r = app
+            .world
+            .spawn(TestPartyGold::default())
+            .set_aggregate_observer::<TestGold, TestGold, TestPartyGold>(vec![b, a])
+            .id();
+
+        let party = app.world.get::<TestPartyGold>(r).unwrap();
+        assert_eq!(party.total, 15);
+        assert_eq!(party.members, vec![b, a]);
+
+        app.update();
+        app.world.get_mut::<TestGold>(a).unwrap().0 = 20;
+        app.update();
+        assert_eq!(app.world.get::<TestPartyGold>(r).unwrap().total, 25);
+
+        app.world.despawn(b);
+        app.update();
+        let party = app.world.get::<TestPartyGold>(r).unwrap();
+        assert_eq!(party.total, 20);
+        assert_eq!(party.members, vec![a]);
+        assert_eq!(
+            app.world
+                .get::<AggregateSubjects<TestGold, TestGold, TestPartyGold>>(r)
+                .unwrap()
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![a]
+        );
+    }
+
+    /// Aggregate links can be removed and cleared from either side, and observers that
+    /// lose O are dropped from their subjects.
+    #[test]
+    fn test_aggregate_lifecycle() {
+        let mut app = App::new();
+        app.register_aggregate_observer::<TestGold, TestGold, TestPartyGold>();
+
+        let a = app.world.spawn(TestGold(10)).id();
+        let b = app.world.spawn(TestGold(5)).id();
+
+        let r = app
+            .world
+            .spawn(TestPartyGold::default())
+            .set_aggregate_observer::<TestGold, TestGold, TestPartyGold>(vec![a, b])
+            .id();
+        let s = app
+            .world
+            .spawn(TestPartyGold::default())
+            .set_aggregate_observer::<TestGold, TestGold, TestPartyGold>(vec![a])
+            .id();
+
+        app.world
+            .entity_mut(r)
+            .remove_aggregate_observer::<TestGold, TestGold, TestPartyGold>(vec![a]);
+        assert_eq!(app.world.get::<TestPartyGold>(r).unwrap().total, 5);
+        assert!(!app
+            .world
+            .get::<AggregatedBy<TestGold, TestGold, TestPartyGold>>(a)
+            .unwrap()
+            .iter()
+            .any(|&observer| observer == r));
+
+        // A change to a only reaches the observers still linked to it.
+        app.update();
+        app.world.get_mut::<TestPartyGold>(r).unwrap().total = 0;
+        app.world.get_mut::<TestGold>(a).unwrap().0 = 20;
+        app.update();
+        assert_eq!(app.world.get::<TestPartyGold>(r).unwrap().total, 0);
+        assert_eq!(app.world.get::<TestPartyGold>(s).unwrap().total, 20);
+
+        app.world
+            .entity_mut(a)
+            .clear_aggregate_observers::<TestGold, TestGold, TestPartyGold>();
+        assert!(!app
+            .world
+            .entity(a)
+            .contains::<AggregatedBy<TestGold, TestGold, TestPartyGold>>());
+        assert!(!app
+            .world
+            .entity(s)
+            .contains::<AggregateSubjects<TestGold, TestGold, TestPartyGold>>());
+        assert_eq!(app.world.get::<TestPartyGold>(s).unwrap().total, 0);
+
+        app.world.entity_mut(r).remove::<TestPartyGold>();
+        app.update();
+        assert!(!app
+            .world
+            .entity(b)
+            .contains::<AggregatedBy<TestGold, TestGold, TestPartyGold>>());
+        assert!(!app
+            .world
+            .entity(r)
+            .contains::<AggregateSubjects<TestGold, TestGold, TestPartyGold>>());
+    }
+
+    #[derive(Component, Default)]
+    struct TestLastWriter(Vec<Entity>);
+
+    impl Observer<String> for TestLastWriter {
+        type Param = ();
+
+        fn receive_data(&mut self, _data: &String, _param: &mut (), sender: Entity) {
+            self.0.push(sender);
+        }
+    }
+
+    /// With SortedByEntity, subjects changed in the same frame are processed in Entity order.
+    #[test]
+    fn test_sorted_ordering() {
+        let mut app = App::new();
+        app.insert_resource(ObserverOrdering::SortedByEntity)
+            .register_observer::<String, TestSubject, TestLastWriter>();
+
+        let subjects: Vec<Entity> = (0..8)
+            .map(|i| {
+                app.world
+                    .spawn(TestSubject {
+                        a: i.to_string(),
+                        b: i,
+                    })
+                    .id()
+            })
+            .collect();
+        let mut reversed = subjects.clone();
+        reversed.reverse();
+
+        let r = app
+            .world
+            .spawn(TestLastWriter::default())
+            .set_observer::<String, TestSubject, TestLastWriter>(reversed)
+            .id();
+        assert_eq!(app.world.get::<TestLastWriter>(r).unwrap().0, subjects);
+
+        app.world.get_mut::<TestLastWriter>(r).unwrap().0.clear();
+        app.update();
+
+        assert_eq!(app.world.get::<TestLastWriter>(r).unwrap().0, subjects);
+    }
+
+    /// Loads a scene holding a subject and one of its two observers into a world that already
+    /// has entities, so the id of the observer left out belongs to another entity there.
+    /// Returns the loaded app, the mapped subject and observer, and the observer left out.
+    fn load_partial_scene(policy: UnmappedLinkPolicy) -> (App, Entity, Entity, Entity) {
+        let mut app = App::new();
+        app.register_observer::<String, TestSubject, TestObserver>();
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+        let r1 = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+        let r2 = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&app.world);
+        builder.extract_entity(g).extract_entity(r1);
+        let scene = builder.build();
+
+        let mut loaded = App::new();
+        loaded
+            .register_observer::<String, TestSubject, TestObserver>()
+            .insert_resource(policy);
+        for _ in 0..10 {
+            loaded.world.spawn_empty();
+        }
+        assert!(loaded.world.get_entity(r2).is_some());
+
+        let mut entity_map = EntityMap::default();
+        scene
+            .write_to_world(&mut loaded.world, &mut entity_map)
+            .unwrap();
+        loaded.update();
+
+        let new_g = entity_map.get(g).unwrap();
+        let new_r1 = entity_map.get(r1).unwrap();
+        (loaded, new_g, new_r1, r2)
+    }
+
+    /// Loading a scene that holds a subject but only some of its observers drops the others,
+    /// even when their ids are taken by other entities in the destination world.
+    #[test]
+    fn test_partial_scene_mapping() {
+        let (app, g, r1, _) = load_partial_scene(UnmappedLinkPolicy::Drop);
+
+        let observers = app
+            .world
+            .get::<ObserverList<String, TestSubject, TestObserver>>(g)
+            .unwrap();
+        assert_eq!(observers.iter().copied().collect::<Vec<_>>(), vec![r1]);
+
+        let observing = app
+            .world
+            .get::<ObservingList<String, TestSubject, TestObserver>>(r1)
+            .unwrap();
+        assert_eq!(observing.iter().copied().collect::<Vec<_>>(), vec![g]);
+    }
+
+    /// The Keep policy leaves unmapped links pointing at the original entity.
+    #[test]
+    fn test_unmapped_link_policy() {
+        let (app, g, r1, r2) = load_partial_scene(UnmappedLinkPolicy::Keep);
+
+        let mut observers: Vec<_> = app
+            .world
+            .get::<ObserverList<String, TestSubject, TestObserver>>(g)
+            .unwrap()
+            .iter()
+            .copied()
+            .collect();
+        observers.sort();
+        let mut expected = vec![r1, r2];
+        expected.sort();
+        assert_eq!(observers, expected);
+    }
+
+    /// Links saved in a .scn.ron file are restored with an initial sync once the scene is loaded,
+    /// and links to subjects left out of the scene are dropped.
+    #[test]
+    fn test_scene_links() {
+        let mut app = App::new();
+        app.register_observer::<String, TestSubject, TestObserver>();
+
+        let g = app
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+        let left_out = app
+            .world
+            .spawn(TestSubject {
+                a: "Left out".to_string(),
+                b: 0,
+            })
+            .id();
+        let r = app
+            .world
+            .spawn(TestObserver::default())
+            .set_observer::<String, TestSubject, TestObserver>(vec![g, left_out])
+            .id();
+
+        app.world.store_scene_links();
+        let links = &app.world.get::<SceneLinks>(r).unwrap().links;
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].name, "TestSubject -> TestObserver: String");
+        assert_eq!(links[0].subjects, vec![g, left_out]);
+
+        let mut builder = DynamicSceneBuilder::from_world(&app.world);
+        builder.extract_entity(g).extract_entity(r);
+        let ron = builder
+            .build()
+            .serialize_ron(app.world.resource::<AppTypeRegistry>())
+            .unwrap();
+        assert!(ron.contains("TestSubject -> TestObserver: String"));
+
+        let mut loaded = App::new();
+        loaded.register_observer::<String, TestSubject, TestObserver>();
+        for _ in 0..10 {
+            loaded.world.spawn_empty();
         }
-    }
 
-    #[derive(Component, Default)]
-    struct TestObserver {
-        a: Option<String>,
-        b: Option<u32>,
+        // The test components aren't reflected, so the scene is loaded onto entities that
+        // already have them, after their first change has gone through.
+        let new_g = loaded
+            .world
+            .spawn(TestSubject {
+                a: "Hello World!".to_string(),
+                b: 42,
+            })
+            .id();
+        let new_r = loaded.world.spawn(TestObserver::default()).id();
+        loaded.update();
+        assert_eq!(loaded.world.get::<TestObserver>(new_r).unwrap().a, None);
+
+        let scene = {
+            let type_registry = loaded.world.resource::<AppTypeRegistry>().read();
+            let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+            SceneDeserializer {
+                type_registry: &type_registry,
+            }
+            .deserialize(&mut deserializer)
+            .unwrap()
+        };
+        let mut entity_map = EntityMap::default();
+        entity_map.insert(g, new_g);
+        entity_map.insert(r, new_r);
+        scene
+            .write_to_world(&mut loaded.world, &mut entity_map)
+            .unwrap();
+        loaded.update();
+
+        assert!(loaded.world.get::<SceneLinks>(new_r).is_none());
+        assert_eq!(
+            loaded.world.get::<TestObserver>(new_r).unwrap().a,
+            Some("Hello World!".to_string())
+        );
+        assert_eq!(
+            loaded
+                .world
+                .get::<ObserverList<String, TestSubject, TestObserver>>(new_g)
+                .unwrap()
+                .to_vec(),
+            vec![new_r]
+        );
+        assert_eq!(
+            loaded
+                .world
+                .get::<ObservingList<String, TestSubject, TestObserver>>(new_r)
+                .unwrap()
+                .to_vec(),
+            vec![new_g]
+        );
     }
 
-    impl Observer<String> for TestObserver {
-        fn receive_data(
-            &mut self,
-            data: &String,
-            _asset_server: &Res<AssetServer>,
-            _sender: Entity,
-        ) {
-            self.a = Some(data.clone());
-        }
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct TestStats {
+        health: f32,
     }
 
-    impl Observer<TestSubject> for TestObserver {
-        fn receive_data(
-            &mut self,
-            data: &TestSubject,
-            _asset_server: &Res<AssetServer>,
-            _sender: Entity,
-        ) {
-            self.a = Some(data.a.clone());
-            self.b = Some(data.b.clone());
-        }
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct TestLabel {
+        value: f32,
+        text: String,
     }
 
-    fn mutate_data(mut query: Query<&mut TestSubject, Added<TestSubject>>) {
-        for mut giver in query.iter_mut() {
-            giver.a = "Farewell World!".to_string();
-            giver.b = 12;
-        }
+    /// Reflection bindings copy fields by path, formatting values bound to Strings.
+    #[test]
+    fn test_reflect_binding() {
+        let mut app = App::new();
+        app.register_reflect_bindings()
+            .register_type::<TestStats>()
+            .register_type::<TestLabel>();
+
+        let g = app.world.spawn(TestStats { health: 10.0 }).id();
+        let r = app
+            .world
+            .spawn((
+                TestLabel::default(),
+                ReflectBindings::new(vec![
+                    ReflectBinding::new(g, "TestStats.health", "TestLabel.value"),
+                    ReflectBinding::new(g, "TestStats.health", "TestLabel.text"),
+                ]),
+            ))
+            .id();
+
+        app.update();
+        let label = app.world.get::<TestLabel>(r).unwrap();
+        assert_eq!(label.value, 10.0);
+        assert_eq!(label.text, "10.0");
+
+        app.world.get_mut::<TestStats>(g).unwrap().health = 5.5;
+        app.update();
+        let label = app.world.get::<TestLabel>(r).unwrap();
+        assert_eq!(label.value, 5.5);
+        assert_eq!(label.text, "5.5");
     }
 
-    /// Quick test to see if mutations are picked up.
-    /// Subject and Observer are registered
-    /// mutate_data changes the subject to something else
-    /// Subject and Observer entities are spawned
-    /// After one frame, check and see if the values match.
+    /// Binding sets link entities selected by Name and marker, and relink them on reload.
     #[test]
-    fn test_data_sync() {
+    fn test_binding_set() {
         let mut app = App::new();
-        app.add_plugin(AssetPlugin::default())
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
             .register_observer::<String, TestSubject, TestObserver>()
-            .add_system(mutate_data);
+            .register_binding_sets()
+            .register_type::<TestStats>();
 
-        let g = app
+        let g1 = app
             .world
-            .spawn(TestSubject {
-                a: "Hello World!".to_string(),
-                b: 42,
-            })
+            .spawn((
+                TestSubject {
+                    a: "Hello World!".to_string(),
+                    b: 42,
+                },
+                Name::new("Greeter"),
+            ))
+            .id();
+        let g2 = app
+            .world
+            .spawn((
+                TestSubject {
+                    a: "Farewell World!".to_string(),
+                    b: 12,
+                },
+                TestStats::default(),
+            ))
             .id();
-
         let r = app
             .world
-            .spawn(TestObserver::default())
-            .set_observer::<String, TestSubject, TestObserver>(vec![g])
+            .spawn((TestObserver::default(), Name::new("Label")))
             .id();
 
+        let binding_set: BindingSet = ron::de::from_str(
+            r#"(
+                links: [
+                    (
+                        link: "TestSubject -> TestObserver: String",
+                        observers: [Name("Label")],
+                        subjects: [Name("Greeter")],
+                    ),
+                ],
+            )"#,
+        )
+        .unwrap();
+        let handle = app
+            .world
+            .resource_mut::<Assets<BindingSet>>()
+            .add(binding_set);
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world.get::<TestObserver>(r).unwrap().a,
+            Some("Hello World!".to_string())
+        );
+        let observing = app
+            .world
+            .get::<ObservingList<String, TestSubject, TestObserver>>(r)
+            .unwrap();
+        assert_eq!(observing.to_vec(), vec![g1]);
+
+        let binding_set: BindingSet = ron::de::from_str(
+            r#"(
+                links: [
+                    (
+                        link: "TestSubject -> TestObserver: String",
+                        observers: [Name("Label")],
+                        subjects: [Marker("TestStats")],
+                    ),
+                ],
+            )"#,
+        )
+        .unwrap();
+        app.world
+            .resource_mut::<Assets<BindingSet>>()
+            .set_untracked(handle.clone(), binding_set);
+        app.update();
         app.update();
 
         assert_eq!(
             app.world.get::<TestObserver>(r).unwrap().a,
             Some("Farewell World!".to_string())
         );
+        let observing = app
+            .world
+            .get::<ObservingList<String, TestSubject, TestObserver>>(r)
+            .unwrap();
+        assert_eq!(observing.to_vec(), vec![g2]);
     }
 
-    /// Same as above, but testing giving the entire component
+    /// Removing a binding set only removes the links it made, not those that were already there.
     #[test]
-    fn test_self_data_sync() {
+    fn test_binding_set_removal() {
         let mut app = App::new();
-        app.add_plugin(AssetPlugin::default())
-            .register_observer::<TestSubject, TestSubject, TestObserver>()
-            .add_system(mutate_data);
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .register_observer::<String, TestSubject, TestObserver>()
+            .register_binding_sets();
+
+        let g1 = app
+            .world
+            .spawn((
+                TestSubject {
+                    a: "Hello World!".to_string(),
+                    b: 42,
+                },
+                Name::new("Greeter"),
+            ))
+            .id();
+        let g2 = app
+            .world
+            .spawn((
+                TestSubject {
+                    a: "Farewell World!".to_string(),
+                    b: 12,
+                },
+                Name::new("Farewell"),
+            ))
+            .id();
+        let r = app
+            .world
+            .spawn((TestObserver::default(), Name::new("Label")))
+            .set_observer::<String, TestSubject, TestObserver>(vec![g1])
+            .id();
+
+        let binding_set: BindingSet = ron::de::from_str(
+            r#"(
+                links: [
+                    (
+                        link: "TestSubject -> TestObserver: String",
+                        observers: [Name("Label")],
+                        subjects: [Name("Greeter"), Name("Farewell")],
+                    ),
+                ],
+            )"#,
+        )
+        .unwrap();
+        let handle = app
+            .world
+            .resource_mut::<Assets<BindingSet>>()
+            .add(binding_set);
+        app.update();
+        app.update();
+
+        let observing = app
+            .world
+            .get::<ObservingList<String, TestSubject, TestObserver>>(r)
+            .unwrap();
+        assert_eq!(observing.to_vec(), vec![g1, g2]);
+
+        app.world
+            .resource_mut::<Assets<BindingSet>>()
+            .remove(&handle);
+        app.update();
+        app.update();
+
+        let observing = app
+            .world
+            .get::<ObservingList<String, TestSubject, TestObserver>>(r)
+            .unwrap();
+        assert_eq!(observing.to_vec(), vec![g1]);
+        assert!(app
+            .world
+            .get::<ObserverList<String, TestSubject, TestObserver>>(g2)
+            .is_none());
+    }
+
+    /// Text observes values directly, and TextFormat formats them into a chosen section.
+    #[cfg(feature = "bevy_ui")]
+    #[test]
+    fn test_text_format() {
+        use crate::TextFormat;
+        use bevy::text::{Text, TextSection};
+
+        let mut app = App::new();
+        app.register_mapped_observer::<u32, TestHealth, Text>()
+            .register_mapped_observer::<(u32, u32), TestHealth, TextFormat>()
+            .register_text_format();
 
         let g = app
             .world
-            .spawn(TestSubject {
-                a: "Hello World!".to_string(),
-                b: 42,
+            .spawn(TestHealth {
+                hp: 80,
+                max_hp: 100,
+            })
+            .id();
+        let r1 = app
+            .world
+            .spawn(Text::default())
+            .set_observer_with::<u32, TestHealth, Text>(vec![g], |health| health.hp)
+            .id();
+        let r2 = app
+            .world
+            .spawn((
+                Text::from_sections([
+                    TextSection::from_style(TextStyle::default()),
+                    TextSection::from_style(TextStyle::default()),
+                ]),
+                TextFormat::new("HP: {}/{}").with_section(1),
+            ))
+            .set_observer_with::<(u32, u32), TestHealth, TextFormat>(vec![g], |health| {
+                (health.hp, health.max_hp)
+            })
+            .id();
+
+        app.update();
+        assert_eq!(app.world.get::<Text>(r1).unwrap().sections[0].value, "80");
+        assert_eq!(
+            app.world.get::<Text>(r2).unwrap().sections[1].value,
+            "HP: 80/100"
+        );
+
+        app.world.get_mut::<TestHealth>(g).unwrap().hp = 35;
+        app.update();
+        assert_eq!(app.world.get::<Text>(r1).unwrap().sections[0].value, "35");
+        let text = app.world.get::<Text>(r2).unwrap();
+        assert_eq!(text.sections[0].value, "");
+        assert_eq!(text.sections[1].value, "HP: 35/100");
+    }
+
+    /// TextFormat::section writes values as they are into a section other than the first.
+    #[cfg(feature = "bevy_ui")]
+    #[test]
+    fn test_text_section() {
+        use crate::TextFormat;
+        use bevy::text::{Text, TextSection};
+
+        let mut app = App::new();
+        app.register_mapped_observer::<u32, TestHealth, TextFormat>()
+            .register_text_format();
+
+        let g = app
+            .world
+            .spawn(TestHealth {
+                hp: 80,
+                max_hp: 100,
             })
             .id();
+        let r = app
+            .world
+            .spawn((
+                Text::from_sections([
+                    TextSection::new("HP: ", TextStyle::default()),
+                    TextSection::from_style(TextStyle::default()),
+                ]),
+                TextFormat::section(1),
+            ))
+            .set_observer_with::<u32, TestHealth, TextFormat>(vec![g], |health| health.hp)
+            .id();
+
+        app.update();
+        let text = app.world.get::<Text>(r).unwrap();
+        assert_eq!(text.sections[0].value, "HP: ");
+        assert_eq!(text.sections[1].value, "80");
+    }
+
+    /// Style observers drive bar widths from ratios, and display, visibility and z-index.
+    #[cfg(feature = "bevy_ui")]
+    #[test]
+    fn test_style_observers() {
+        use crate::{StyleDimension, StyleRatio};
+
+        let mut app = App::new();
+        app.register_mapped_observer::<f32, TestHealth, StyleRatio>()
+            .register_mapped_observer::<bool, TestHealth, Style>()
+            .register_mapped_observer::<bool, TestHealth, Visibility>()
+            .register_mapped_observer::<i32, TestHealth, ZIndex>()
+            .register_style_ratio();
 
+        let g = app
+            .world
+            .spawn(TestHealth {
+                hp: 80,
+                max_hp: 100,
+            })
+            .id();
         let r = app
             .world
-            .spawn(TestObserver::default())
-            .set_observer::<TestSubject, TestSubject, TestObserver>(vec![g])
+            .spawn((
+                Style::default(),
+                StyleRatio::new(StyleDimension::Width),
+                Visibility::default(),
+                ZIndex::Global(0),
+            ))
+            .set_observer_with::<f32, TestHealth, StyleRatio>(vec![g], |health| {
+                health.hp as f32 / health.max_hp as f32
+            })
+            .set_observer_with::<bool, TestHealth, Style>(vec![g], |health| health.hp > 0)
+            .set_observer_with::<bool, TestHealth, Visibility>(vec![g], |health| health.hp > 0)
+            .set_observer_with::<i32, TestHealth, ZIndex>(vec![g], |health| health.hp as i32)
+            .id();
+
+        app.update();
+        let style = app.world.get::<Style>(r).unwrap();
+        assert_eq!(style.size.width, Val::Percent(80.0));
+        assert_eq!(style.display, Display::Flex);
+        assert!(matches!(
+            app.world.get::<ZIndex>(r),
+            Some(ZIndex::Global(80))
+        ));
+
+        app.world.get_mut::<TestHealth>(g).unwrap().hp = 0;
+        app.update();
+        let style = app.world.get::<Style>(r).unwrap();
+        assert_eq!(style.size.width, Val::Percent(0.0));
+        assert_eq!(style.display, Display::None);
+        assert_eq!(app.world.get::<Visibility>(r), Some(&Visibility::Hidden));
+        assert!(matches!(
+            app.world.get::<ZIndex>(r),
+            Some(ZIndex::Global(0))
+        ));
+    }
+
+    /// Sprite observers set color, atlas index and image.
+    #[cfg(feature = "bevy_sprite")]
+    #[test]
+    fn test_sprite_observers() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin::default())
+            .register_mapped_observer::<Color, TestHealth, Sprite>()
+            .register_mapped_observer::<usize, TestHealth, TextureAtlasSprite>()
+            .register_mapped_observer::<String, TestHealth, Handle<Image>>();
+
+        let g = app.world.spawn(TestHealth { hp: 2, max_hp: 3 }).id();
+        let r = app
+            .world
+            .spawn((
+                Sprite::default(),
+                TextureAtlasSprite::default(),
+                Handle::<Image>::default(),
+            ))
+            .set_observer_with::<Color, TestHealth, Sprite>(vec![g], |health| match health.hp {
+                0 => Color::RED,
+                _ => Color::GREEN,
+            })
+            .set_observer_with::<usize, TestHealth, TextureAtlasSprite>(vec![g], |health| {
+                health.hp as usize
+            })
+            .set_observer_with::<String, TestHealth, Handle<Image>>(vec![g], |health| {
+                format!("hearts/{}.png", health.hp)
+            })
+            .id();
+
+        app.update();
+        let hearts: Handle<Image> = app
+            .world
+            .resource::<AssetServer>()
+            .get_handle("hearts/2.png");
+        assert_eq!(app.world.get::<Sprite>(r).unwrap().color, Color::GREEN);
+        assert_eq!(app.world.get::<TextureAtlasSprite>(r).unwrap().index, 2);
+        assert_eq!(app.world.get::<Handle<Image>>(r), Some(&hearts));
+
+        app.world.get_mut::<TestHealth>(g).unwrap().hp = 0;
+        app.update();
+        let hearts: Handle<Image> = app
+            .world
+            .resource::<AssetServer>()
+            .get_handle("hearts/0.png");
+        assert_eq!(app.world.get::<Sprite>(r).unwrap().color, Color::RED);
+        assert_eq!(app.world.get::<TextureAtlasSprite>(r).unwrap().index, 0);
+        assert_eq!(app.world.get::<Handle<Image>>(r), Some(&hearts));
+    }
+
+    #[cfg(feature = "transform")]
+    #[derive(Component)]
+    struct TestPosition(Vec3);
+
+    #[cfg(feature = "transform")]
+    impl Subject<Vec3> for TestPosition {
+        fn give_data(&self) -> &Vec3 {
+            &self.0
+        }
+    }
+
+    /// Follow moves the Transform to the followed translation plus its offset.
+    #[cfg(feature = "transform")]
+    #[test]
+    fn test_follow() {
+        use crate::Follow;
+
+        let mut app = App::new();
+        app.register_observer::<Transform, Transform, Follow>()
+            .register_observer::<GlobalTransform, GlobalTransform, Follow>()
+            .register_observer::<Vec3, TestPosition, Follow>()
+            .register_follow();
+
+        let player = app.world.spawn(Transform::from_xyz(1.0, 2.0, 0.0)).id();
+        let unit = app
+            .world
+            .spawn(GlobalTransform::from_xyz(-4.0, 0.0, 0.0))
+            .id();
+        let marker = app.world.spawn(TestPosition(Vec3::new(3.0, 3.0, 0.0))).id();
+        let camera = app
+            .world
+            .spawn((Transform::default(), Follow::new(Vec3::new(0.0, 0.0, 10.0))))
+            .set_observer::<Transform, Transform, Follow>(vec![player])
+            .id();
+        let nameplate = app
+            .world
+            .spawn((Transform::default(), Follow::new(Vec3::Y)))
+            .set_observer::<GlobalTransform, GlobalTransform, Follow>(vec![unit])
+            .id();
+        let pointer = app
+            .world
+            .spawn((Transform::default(), Follow::default()))
+            .set_observer::<Vec3, TestPosition, Follow>(vec![marker])
             .id();
 
         app.update();
+        assert_eq!(
+            app.world.get::<Transform>(camera).unwrap().translation,
+            Vec3::new(1.0, 2.0, 10.0)
+        );
+        assert_eq!(
+            app.world.get::<Transform>(nameplate).unwrap().translation,
+            Vec3::new(-4.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            app.world.get::<Transform>(pointer).unwrap().translation,
+            Vec3::new(3.0, 3.0, 0.0)
+        );
 
+        app.world
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation
+            .x = 5.0;
+        app.update();
         assert_eq!(
-            app.world.get::<TestObserver>(r).unwrap().a,
-            Some("Farewell World!".to_string())
+            app.world.get::<Transform>(camera).unwrap().translation,
+            Vec3::new(5.0, 2.0, 10.0)
         );
-        assert_eq!(app.world.get::<TestObserver>(r).unwrap().b, Some(12));
+        assert_eq!(
+            app.world.get::<Follow>(camera).unwrap().target(),
+            Some(Vec3::new(5.0, 2.0, 0.0))
+        );
+    }
+
+    #[derive(Component)]
+    struct TestVolume {
+        value: f32,
+        received: u32,
+    }
+
+    impl Subject<f32> for TestVolume {
+        fn give_data(&self) -> &f32 {
+            &self.value
+        }
+    }
+
+    impl Observer<f32> for TestVolume {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &f32, _param: &mut (), _sender: Entity) {
+            self.value = *data;
+            self.received += 1;
+        }
+    }
+
+    #[derive(Component, Default)]
+    struct TestSlider {
+        value: f32,
+        received: u32,
+    }
+
+    impl Subject<f32> for TestSlider {
+        fn give_data(&self) -> &f32 {
+            &self.value
+        }
+    }
+
+    impl Observer<f32> for TestSlider {
+        type Param = ();
+
+        fn receive_data(&mut self, data: &f32, _param: &mut (), _sender: Entity) {
+            self.value = *data;
+            self.received += 1;
+        }
+    }
+
+    /// Edits on either side of a two-way link reach the others once, without echoing back.
+    #[test]
+    fn test_two_way() {
+        let mut app = App::new();
+        app.register_two_way_observer::<f32, TestVolume, TestSlider>();
+
+        let g = app
+            .world
+            .spawn(TestVolume {
+                value: 0.5,
+                received: 0,
+            })
+            .id();
+        let r1 = app
+            .world
+            .spawn(TestSlider::default())
+            .set_two_way_observer::<f32, TestVolume, TestSlider>(vec![g])
+            .id();
+        let r2 = app
+            .world
+            .spawn(TestSlider::default())
+            .set_two_way_observer::<f32, TestVolume, TestSlider>(vec![g])
+            .id();
+
+        let received = |app: &App| {
+            (
+                app.world.get::<TestVolume>(g).unwrap().received,
+                app.world.get::<TestSlider>(r1).unwrap().received,
+                app.world.get::<TestSlider>(r2).unwrap().received,
+            )
+        };
+
+        app.update();
+        app.update();
+        assert_eq!(app.world.get::<TestSlider>(r1).unwrap().value, 0.5);
+        assert_eq!(app.world.get::<TestSlider>(r2).unwrap().value, 0.5);
+        assert_eq!(received(&app), (0, 1, 1));
+
+        app.world.get_mut::<TestSlider>(r1).unwrap().value = 0.8;
+        app.update();
+        assert_eq!(app.world.get::<TestVolume>(g).unwrap().value, 0.8);
+        app.update();
+        assert_eq!(app.world.get::<TestSlider>(r2).unwrap().value, 0.8);
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world.get::<TestSlider>(r1).unwrap().value, 0.8);
+        assert_eq!(received(&app), (1, 1, 2));
+
+        app.world.get_mut::<TestVolume>(g).unwrap().value = 0.2;
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world.get::<TestSlider>(r1).unwrap().value, 0.2);
+        assert_eq!(app.world.get::<TestSlider>(r2).unwrap().value, 0.2);
+        assert_eq!(received(&app), (1, 2, 3));
     }
 }
@@ -0,0 +1,399 @@
+use std::{marker::PhantomData, ops::Deref, sync::Arc};
+
+use bevy::{
+    ecs::system::{Command, StaticSystemParam, SystemState},
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use crate::{Observer, ObserverLinkDropped, ObserverOrdering};
+
+/// Computes the value an observer receives from a subject component.
+pub type MapFn<S, T> = Arc<dyn Fn(&S) -> T + Send + Sync>;
+
+/// List of entities observing this entity through a mapping closure.
+/// Each link keeps its own closure, so the same S can feed different values to different observers.
+#[derive(Component)]
+pub struct MappedObserverList<T: Send + Sync + 'static, S: Component, O: Observer<T>> {
+    observers: HashMap<Entity, MapFn<S, T>>,
+
+    phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> MappedObserverList<T, S, O> {
+    pub fn new(list: impl IntoIterator<Item = (Entity, MapFn<S, T>)>) -> Self {
+        MappedObserverList {
+            observers: list.into_iter().collect(),
+            phantom_observer: PhantomData,
+        }
+    }
+
+    /// Returns true if the entity observes this subject through a mapping closure.
+    pub fn contains(&self, observer: &Entity) -> bool {
+        self.observers.contains_key(observer)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.observers.keys()
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Default
+    for MappedObserverList<T, S, O>
+{
+    fn default() -> Self {
+        MappedObserverList::new(vec![])
+    }
+}
+
+/// List of entities that this entity is observing through a mapping closure.
+/// Kept in sync with the subjects' MappedObserverList by the mapped observer commands.
+#[derive(Component)]
+pub struct MappedObservingList<T: Send + Sync + 'static, S: Component, O: Observer<T>> {
+    subjects: HashSet<Entity>,
+
+    phantom_data: PhantomData<T>,
+    phantom_subject: PhantomData<S>,
+    phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Deref
+    for MappedObservingList<T, S, O>
+{
+    type Target = HashSet<Entity>;
+    fn deref(&self) -> &Self::Target {
+        &self.subjects
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> MappedObservingList<T, S, O> {
+    pub fn new(list: impl IntoIterator<Item = Entity>) -> Self {
+        MappedObservingList {
+            subjects: list.into_iter().collect(),
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Default
+    for MappedObservingList<T, S, O>
+{
+    fn default() -> Self {
+        MappedObservingList::new(vec![])
+    }
+}
+
+/// Removes the given subjects from the observer's MappedObservingList, dropping the list when empty.
+fn unlink_mapped_subjects<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    world: &mut World,
+    observer: Entity,
+    subjects: impl IntoIterator<Item = Entity>,
+) {
+    let Some(mut entity_mut) = world.get_entity_mut(observer) else {
+        return;
+    };
+    let Some(mut observing_list) = entity_mut.get_mut::<MappedObservingList<T, S, O>>() else {
+        return;
+    };
+    for subject in subjects {
+        observing_list.subjects.remove(&subject);
+    }
+    if observing_list.subjects.is_empty() {
+        entity_mut.remove::<MappedObservingList<T, S, O>>();
+    }
+}
+
+pub(crate) struct MappedObserverBuildCommand<T: Send + Sync + 'static, S: Component, O: Observer<T>>
+{
+    pub observer: Entity,
+    pub subjects: Vec<Entity>,
+    pub map: MapFn<S, T>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Command
+    for MappedObserverBuildCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        crate::registry::warn_link_cycles::<S, O>(world, self.observer, &self.subjects);
+
+        for &source in self.subjects.iter() {
+            let mut entity_mut = world.entity_mut(source);
+            match entity_mut.get_mut::<MappedObserverList<T, S, O>>() {
+                Some(mut observer_list) => {
+                    observer_list
+                        .observers
+                        .insert(self.observer, self.map.clone());
+                }
+                None => {
+                    entity_mut.insert(MappedObserverList::<T, S, O>::new(vec![(
+                        self.observer,
+                        self.map.clone(),
+                    )]));
+                }
+            }
+        }
+
+        if let Some(mut entity_mut) = world.get_entity_mut(self.observer) {
+            match entity_mut.get_mut::<MappedObservingList<T, S, O>>() {
+                Some(mut observing_list) => {
+                    observing_list
+                        .subjects
+                        .extend(self.subjects.iter().copied());
+                }
+                None => {
+                    entity_mut.insert(MappedObservingList::<T, S, O>::new(self.subjects.clone()));
+                }
+            }
+        }
+
+        let mut subjects = self.subjects;
+        if let Some(ordering) = world.get_resource::<ObserverOrdering>() {
+            ordering.sort_by_entity(&mut subjects, |subject| *subject);
+        }
+
+        let mut system_state: SystemState<(
+            StaticSystemParam<O::Param>,
+            Query<&mut O>,
+            Query<(Entity, &S)>,
+        )> = SystemState::new(world);
+
+        {
+            let (mut param, mut observer_query, subject_query) = system_state.get_mut(world);
+
+            if let Ok(mut observer) = observer_query.get_mut(self.observer) {
+                for &source in subjects.iter() {
+                    if let Ok((subject, subject_comp)) = subject_query.get(source) {
+                        let data = (self.map)(subject_comp);
+                        observer.receive_data(&data, &mut param, subject)
+                    }
+                }
+            }
+        }
+
+        system_state.apply(world);
+    }
+}
+
+pub(crate) struct MappedObserverRemoveCommand<
+    T: Send + Sync + 'static,
+    S: Component,
+    O: Observer<T>,
+> {
+    pub observer: Entity,
+    pub subjects: Vec<Entity>,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<S>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Command
+    for MappedObserverRemoveCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        for &source in self.subjects.iter() {
+            let Some(mut entity_mut) = world.get_entity_mut(source) else {
+                continue;
+            };
+            let Some(mut observer_list) = entity_mut.get_mut::<MappedObserverList<T, S, O>>()
+            else {
+                continue;
+            };
+            observer_list.observers.remove(&self.observer);
+            if observer_list.observers.is_empty() {
+                entity_mut.remove::<MappedObserverList<T, S, O>>();
+            }
+        }
+
+        unlink_mapped_subjects::<T, S, O>(world, self.observer, self.subjects);
+    }
+}
+
+pub(crate) struct MappedObserverClearCommand<T: Send + Sync + 'static, S: Component, O: Observer<T>>
+{
+    pub subject: Entity,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<S>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, S: Component, O: Observer<T>> Command
+    for MappedObserverClearCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        let Some(mut entity_mut) = world.get_entity_mut(self.subject) else {
+            return;
+        };
+        let Some(observer_list) = entity_mut.take::<MappedObserverList<T, S, O>>() else {
+            return;
+        };
+        for &observer in observer_list.observers.keys() {
+            unlink_mapped_subjects::<T, S, O>(world, observer, [self.subject]);
+        }
+    }
+}
+
+pub(crate) fn observers_in_mapped_list<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    world: &World,
+    subject: Entity,
+) -> Vec<Entity> {
+    world
+        .get::<MappedObserverList<T, S, O>>(subject)
+        .map(|list| list.observers.keys().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Runs each link's mapping closure on changed subjects and updates the observers.
+/// Links to observers that were despawned or lost O are dropped.
+pub(crate) fn recieve_mapped_subject_event<
+    T: Send + Sync + 'static,
+    S: Component,
+    O: Observer<T>,
+>(
+    mut commands: Commands,
+    mut param: StaticSystemParam<O::Param>,
+    mut observer_query: Query<&mut O>,
+    mut observer_list_query: Query<(Entity, &S, &mut MappedObserverList<T, S, O>), Changed<S>>,
+    mut dropped_events: EventWriter<ObserverLinkDropped>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let mut changed: Vec<_> = observer_list_query.iter_mut().collect();
+    ordering.sort_by_entity(&mut changed, |(subject, ..)| *subject);
+
+    for (subject, subject_comp, mut observer_list) in changed {
+        let mut links: Vec<_> = observer_list
+            .observers
+            .iter()
+            .map(|(&observer, map)| (observer, map.clone()))
+            .collect();
+        ordering.sort_by_entity(&mut links, |(observer, _)| *observer);
+
+        for (observer, map) in links {
+            match observer_query.get_mut(observer) {
+                Ok(mut observer) => {
+                    observer.receive_data(&map(subject_comp), &mut param, subject);
+                }
+                Err(_) => drop_mapped_link(
+                    &mut commands,
+                    subject,
+                    &mut observer_list,
+                    observer,
+                    &mut dropped_events,
+                ),
+            }
+        }
+    }
+}
+
+/// Drops the link from the subject to the observer on both sides,
+/// removing lists left empty and sending ObserverLinkDropped.
+fn drop_mapped_link<T: Send + Sync + 'static, S: Component, O: Observer<T>>(
+    commands: &mut Commands,
+    subject: Entity,
+    observer_list: &mut MappedObserverList<T, S, O>,
+    observer: Entity,
+    dropped_events: &mut EventWriter<ObserverLinkDropped>,
+) {
+    if observer_list.observers.remove(&observer).is_none() {
+        return;
+    }
+    debug!(
+        "Dropped stale mapped observer link {:?} -> {:?}",
+        subject, observer
+    );
+    dropped_events.send(ObserverLinkDropped { subject, observer });
+
+    if observer_list.observers.is_empty() {
+        commands
+            .entity(subject)
+            .remove::<MappedObserverList<T, S, O>>();
+    }
+    commands.add(move |world: &mut World| {
+        unlink_mapped_subjects::<T, S, O>(world, observer, [subject]);
+    });
+}
+
+/// Removes observers that lost their O component from every MappedObserverList.
+pub(crate) fn cleanup_removed_mapped_observers<
+    T: Send + Sync + 'static,
+    S: Component,
+    O: Observer<T>,
+>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<O>,
+    mut observer_list_query: Query<(Entity, &mut MappedObserverList<T, S, O>)>,
+    observing_list_query: Query<(), With<MappedObservingList<T, S, O>>>,
+    mut dropped_events: EventWriter<ObserverLinkDropped>,
+) {
+    let removed: HashSet<Entity> = removed.iter().collect();
+    if removed.is_empty() {
+        return;
+    }
+
+    for (subject, mut observer_list) in observer_list_query.iter_mut() {
+        for &observer in removed.iter() {
+            drop_mapped_link(
+                &mut commands,
+                subject,
+                &mut observer_list,
+                observer,
+                &mut dropped_events,
+            );
+        }
+    }
+
+    for &observer in removed.iter() {
+        if observing_list_query.contains(observer) {
+            commands
+                .entity(observer)
+                .remove::<MappedObservingList<T, S, O>>();
+        }
+    }
+}
+
+/// Tells mapped observers about subjects that were despawned or lost their S component.
+pub(crate) fn notify_lost_mapped_subjects<
+    T: Send + Sync + 'static,
+    S: Component,
+    O: Observer<T>,
+>(
+    mut commands: Commands,
+    mut param: StaticSystemParam<O::Param>,
+    mut removed: RemovedComponents<S>,
+    mut observer_query: Query<(Entity, &mut O, &mut MappedObservingList<T, S, O>)>,
+    observer_list_query: Query<(), With<MappedObserverList<T, S, O>>>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let removed: HashSet<Entity> = removed.iter().collect();
+    if removed.is_empty() {
+        return;
+    }
+    let mut removed_in_order: Vec<Entity> = removed.iter().copied().collect();
+    ordering.sort_by_entity(&mut removed_in_order, |subject| *subject);
+
+    for (observer, mut observer_comp, mut observing_list) in observer_query.iter_mut() {
+        if observing_list.subjects.is_disjoint(&removed) {
+            continue;
+        }
+        for &subject in removed_in_order.iter() {
+            if observing_list.subjects.remove(&subject) {
+                observer_comp.on_subject_lost(&mut param, subject);
+            }
+        }
+        if observing_list.subjects.is_empty() {
+            commands
+                .entity(observer)
+                .remove::<MappedObservingList<T, S, O>>();
+        }
+    }
+
+    for &subject in removed.iter() {
+        if observer_list_query.contains(subject) {
+            commands
+                .entity(subject)
+                .remove::<MappedObserverList<T, S, O>>();
+        }
+    }
+}
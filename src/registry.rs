@@ -0,0 +1,186 @@
+use std::any::{type_name, TypeId};
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+/// Type id and name of a component or resource taking part in a registered link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComponentInfo {
+    pub id: TypeId,
+    pub name: &'static str,
+}
+
+impl ComponentInfo {
+    pub fn of<C: 'static>() -> Self {
+        ComponentInfo {
+            id: TypeId::of::<C>(),
+            name: type_name::<C>(),
+        }
+    }
+}
+
+/// A registered (T, S, O) triple.
+#[derive(Clone)]
+pub struct RegisteredLink {
+    pub data: ComponentInfo,
+    pub subject: ComponentInfo,
+    pub observer: ComponentInfo,
+
+    /// Lists the observers linked to a subject entity through this triple.
+    observers_of: fn(&World, Entity) -> Vec<Entity>,
+}
+
+impl RegisteredLink {
+    pub fn new<T: 'static, S: 'static, O: 'static>(
+        observers_of: fn(&World, Entity) -> Vec<Entity>,
+    ) -> Self {
+        RegisteredLink {
+            data: ComponentInfo::of::<T>(),
+            subject: ComponentInfo::of::<S>(),
+            observer: ComponentInfo::of::<O>(),
+            observers_of,
+        }
+    }
+
+    /// Observers linked to the subject entity through this triple.
+    pub fn observers_of(&self, world: &World, subject: Entity) -> Vec<Entity> {
+        (self.observers_of)(world, subject)
+    }
+}
+
+/// Every (T, S, O) triple registered on the app, forming a graph from subject to observer types.
+#[derive(Resource, Default, Clone)]
+pub struct ObserverRegistry {
+    links: Vec<RegisteredLink>,
+}
+
+impl ObserverRegistry {
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredLink> {
+        self.links.iter()
+    }
+
+    /// Adds a triple to the registry.
+    /// Returns the closed cycle of component names if the new link closes a loop between types.
+    pub fn register(&mut self, link: RegisteredLink) -> Result<(), Vec<&'static str>> {
+        let duplicate = self.links.iter().any(|l| {
+            l.data == link.data && l.subject == link.subject && l.observer == link.observer
+        });
+        let cycle = self
+            .find_type_path(link.observer, link.subject)
+            .map(|mut path| {
+                path.push(link.observer.name);
+                path
+            });
+        if !duplicate {
+            self.links.push(link);
+        }
+
+        match cycle {
+            Some(cycle) => Err(cycle),
+            None => Ok(()),
+        }
+    }
+
+    /// Component names along registered links from `from` to `to`, both included.
+    fn find_type_path(&self, from: ComponentInfo, to: ComponentInfo) -> Option<Vec<&'static str>> {
+        let mut parents = HashMap::<TypeId, ComponentInfo>::default();
+        let mut stack = vec![from];
+        let mut visited = HashSet::<TypeId>::default();
+        visited.insert(from.id);
+
+        while let Some(current) = stack.pop() {
+            if current.id == to.id {
+                let mut path = vec![current.name];
+                let mut node = current.id;
+                while let Some(parent) = parents.get(&node) {
+                    path.push(parent.name);
+                    node = parent.id;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for link in self.links.iter().filter(|l| l.subject.id == current.id) {
+                if visited.insert(link.observer.id) {
+                    parents.insert(link.observer.id, current);
+                    stack.push(link.observer);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Looks for a chain of existing links leading from `from` back to `to`, where each node is
+    /// an entity together with the component observing or being observed.
+    /// Returns the nodes along the chain, both ends included.
+    pub fn find_link_path(
+        &self,
+        world: &World,
+        from: (Entity, ComponentInfo),
+        to: (Entity, ComponentInfo),
+    ) -> Option<Vec<(Entity, &'static str)>> {
+        type Node = (Entity, TypeId);
+
+        let mut parents = HashMap::<Node, (Entity, ComponentInfo)>::default();
+        let mut stack = vec![from];
+        let mut visited = HashSet::<Node>::default();
+        visited.insert((from.0, from.1.id));
+
+        while let Some((entity, component)) = stack.pop() {
+            if entity == to.0 && component.id == to.1.id {
+                let mut path = vec![(entity, component.name)];
+                let mut node = (entity, component.id);
+                while let Some(&(parent, parent_component)) = parents.get(&node) {
+                    path.push((parent, parent_component.name));
+                    node = (parent, parent_component.id);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for link in self.links.iter().filter(|l| l.subject.id == component.id) {
+                for observer in link.observers_of(world, entity) {
+                    if visited.insert((observer, link.observer.id)) {
+                        parents.insert((observer, link.observer.id), (entity, component));
+                        stack.push((observer, link.observer));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Formats a chain of linked entities for cycle warnings.
+pub(crate) fn format_link_path(path: &[(Entity, &'static str)]) -> String {
+    path.iter()
+        .map(|(entity, name)| format!("{:?} {}", entity, name))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// Warns about every subject whose link to the observer would close a loop of existing links.
+pub(crate) fn warn_link_cycles<S: Component, O: Component>(
+    world: &World,
+    observer: Entity,
+    subjects: &[Entity],
+) {
+    let Some(registry) = world.get_resource::<ObserverRegistry>() else {
+        return;
+    };
+    for &subject in subjects {
+        if let Some(mut path) = registry.find_link_path(
+            world,
+            (observer, ComponentInfo::of::<O>()),
+            (subject, ComponentInfo::of::<S>()),
+        ) {
+            path.push((observer, type_name::<O>()));
+            warn!(
+                "Observer link closes a cycle, changes will bounce around it every frame: {}",
+                format_link_path(&path)
+            );
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::system::{Command, StaticSystemParam, SystemParam, SystemParamItem, SystemState},
+    prelude::*,
+};
+
+use crate::{Observer, ObserverOrdering, Subject};
+
+/// Sender passed to observers when the data comes from a resource instead of an entity.
+pub const RESOURCE_SUBJECT: Entity = Entity::from_raw(u32::MAX);
+
+/// Marks a resource as a possible Subject that can give T
+/// All resources automatically implement this for T = Self
+pub trait ResourceSubject<T: Send + Sync + 'static>: Resource {
+    fn give_data(&self) -> &T;
+}
+
+impl<T: Resource> ResourceSubject<T> for T {
+    fn give_data(&self) -> &T {
+        self
+    }
+}
+
+/// An observer resource. Mutated subjects it observes through set_resource_as_observer
+/// will update this resource.
+pub trait ResourceObserver<T: Send + Sync + 'static>: Resource {
+    /// System params fetched alongside the resource and passed to receive_data.
+    /// Use `()` if the resource needs nothing from the world.
+    type Param: SystemParam + 'static;
+
+    fn receive_data(&mut self, data: &T, param: &mut SystemParamItem<Self::Param>, sender: Entity);
+}
+
+/// Marks this entity's O component as observing the resource R.
+#[derive(Reflect, FromReflect, Clone, Component)]
+#[reflect(Component)]
+pub struct ObservingResource<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>> {
+    #[reflect(ignore)]
+    phantom_data: PhantomData<T>,
+
+    #[reflect(ignore)]
+    phantom_subject: PhantomData<R>,
+
+    #[reflect(ignore)]
+    phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>> Default
+    for ObservingResource<T, R, O>
+{
+    fn default() -> Self {
+        ObservingResource {
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+    }
+}
+
+pub(crate) struct ResourceObserverBuildCommand<
+    T: Send + Sync + 'static,
+    R: ResourceSubject<T>,
+    O: Observer<T>,
+> {
+    pub observer: Entity,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<R>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>> Command
+    for ResourceObserverBuildCommand<T, R, O>
+{
+    fn write(self, world: &mut World) {
+        world
+            .entity_mut(self.observer)
+            .insert(ObservingResource::<T, R, O>::default());
+
+        let mut system_state: SystemState<(
+            StaticSystemParam<O::Param>,
+            Option<Res<R>>,
+            Query<&mut O>,
+        )> = SystemState::new(world);
+
+        {
+            let (mut param, resource, mut observer_query) = system_state.get_mut(world);
+
+            if let (Some(resource), Ok(mut observer)) =
+                (resource, observer_query.get_mut(self.observer))
+            {
+                let data = ResourceSubject::<T>::give_data(&*resource);
+                observer.receive_data(data, &mut param, RESOURCE_SUBJECT);
+            }
+        }
+
+        system_state.apply(world);
+    }
+}
+
+pub(crate) struct ResourceObserverRemoveCommand<
+    T: Send + Sync + 'static,
+    R: ResourceSubject<T>,
+    O: Observer<T>,
+> {
+    pub observer: Entity,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<R>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<T: Send + Sync + 'static, R: ResourceSubject<T>, O: Observer<T>> Command
+    for ResourceObserverRemoveCommand<T, R, O>
+{
+    fn write(self, world: &mut World) {
+        if let Some(mut entity_mut) = world.get_entity_mut(self.observer) {
+            entity_mut.remove::<ObservingResource<T, R, O>>();
+        }
+    }
+}
+
+/// Updates every observer of R when the resource changes.
+pub(crate) fn recieve_resource_event<
+    T: Send + Sync + 'static,
+    R: ResourceSubject<T>,
+    O: Observer<T>,
+>(
+    resource: Option<Res<R>>,
+    mut param: StaticSystemParam<O::Param>,
+    mut observer_query: Query<(Entity, &mut O), With<ObservingResource<T, R, O>>>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let Some(resource) = resource else {
+        return;
+    };
+    if !resource.is_changed() {
+        return;
+    }
+
+    let data = ResourceSubject::<T>::give_data(&*resource);
+    let mut observers: Vec<_> = observer_query.iter_mut().collect();
+    ordering.sort_by_entity(&mut observers, |(observer, _)| *observer);
+
+    for (_, mut observer) in observers {
+        observer.receive_data(data, &mut param, RESOURCE_SUBJECT);
+    }
+}
+
+/// Marks this entity's S component as observed by the resource R.
+#[derive(Reflect, FromReflect, Clone, Component)]
+#[reflect(Component)]
+pub struct ObservedByResource<T: Send + Sync + 'static, S: Subject<T>, R: ResourceObserver<T>> {
+    #[reflect(ignore)]
+    phantom_data: PhantomData<T>,
+
+    #[reflect(ignore)]
+    phantom_subject: PhantomData<S>,
+
+    #[reflect(ignore)]
+    phantom_observer: PhantomData<R>,
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, R: ResourceObserver<T>> Default
+    for ObservedByResource<T, S, R>
+{
+    fn default() -> Self {
+        ObservedByResource {
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+    }
+}
+
+struct ResourceAsObserverBuildCommand<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    R: ResourceObserver<T>,
+> {
+    pub subjects: Vec<Entity>,
+    phantom_data: PhantomData<T>,
+    phantom_subject: PhantomData<S>,
+    phantom_observer: PhantomData<R>,
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, R: ResourceObserver<T>> Command
+    for ResourceAsObserverBuildCommand<T, S, R>
+{
+    fn write(self, world: &mut World) {
+        for &source in self.subjects.iter() {
+            world
+                .entity_mut(source)
+                .insert(ObservedByResource::<T, S, R>::default());
+        }
+
+        let mut subjects = self.subjects;
+        if let Some(ordering) = world.get_resource::<ObserverOrdering>() {
+            ordering.sort_by_entity(&mut subjects, |subject| *subject);
+        }
+
+        let mut system_state: SystemState<(
+            StaticSystemParam<R::Param>,
+            Option<ResMut<R>>,
+            Query<(Entity, &S)>,
+        )> = SystemState::new(world);
+
+        {
+            let (mut param, resource, subject_query) = system_state.get_mut(world);
+
+            if let Some(mut resource) = resource {
+                for &source in subjects.iter() {
+                    if let Ok((subject, subject_comp)) = subject_query.get(source) {
+                        let data = Subject::<T>::give_data(subject_comp);
+                        resource.receive_data(data, &mut param, subject);
+                    }
+                }
+            }
+        }
+
+        system_state.apply(world);
+    }
+}
+
+struct ResourceAsObserverRemoveCommand<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    R: ResourceObserver<T>,
+> {
+    pub subjects: Vec<Entity>,
+    phantom_data: PhantomData<T>,
+    phantom_subject: PhantomData<S>,
+    phantom_observer: PhantomData<R>,
+}
+
+impl<T: Send + Sync + 'static, S: Subject<T>, R: ResourceObserver<T>> Command
+    for ResourceAsObserverRemoveCommand<T, S, R>
+{
+    fn write(self, world: &mut World) {
+        for &source in self.subjects.iter() {
+            if let Some(mut entity_mut) = world.get_entity_mut(source) {
+                entity_mut.remove::<ObservedByResource<T, S, R>>();
+            }
+        }
+    }
+}
+
+pub trait ResourceObserverCommandExt {
+    /// Sets the resource R to observe component S on the source entities.
+    fn set_resource_as_observer<T: Send + Sync + 'static, S: Subject<T>, R: ResourceObserver<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
+
+    /// Stops the resource R from observing component S on the source entities.
+    fn remove_resource_as_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        R: ResourceObserver<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self;
+}
+
+impl<'w, 's> ResourceObserverCommandExt for Commands<'w, 's> {
+    fn set_resource_as_observer<T: Send + Sync + 'static, S: Subject<T>, R: ResourceObserver<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        self.add(ResourceAsObserverBuildCommand::<T, S, R> {
+            subjects: sources,
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        });
+
+        self
+    }
+
+    fn remove_resource_as_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        R: ResourceObserver<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        self.add(ResourceAsObserverRemoveCommand::<T, S, R> {
+            subjects: sources,
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        });
+
+        self
+    }
+}
+
+impl ResourceObserverCommandExt for World {
+    fn set_resource_as_observer<T: Send + Sync + 'static, S: Subject<T>, R: ResourceObserver<T>>(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        ResourceAsObserverBuildCommand::<T, S, R> {
+            subjects: sources,
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+        .write(self);
+
+        self
+    }
+
+    fn remove_resource_as_observer<
+        T: Send + Sync + 'static,
+        S: Subject<T>,
+        R: ResourceObserver<T>,
+    >(
+        &mut self,
+        sources: Vec<Entity>,
+    ) -> &mut Self {
+        ResourceAsObserverRemoveCommand::<T, S, R> {
+            subjects: sources,
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+        .write(self);
+
+        self
+    }
+}
+
+/// Updates the resource R from every changed subject it observes.
+pub(crate) fn recieve_subject_event_for_resource<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    R: ResourceObserver<T>,
+>(
+    resource: Option<ResMut<R>>,
+    mut param: StaticSystemParam<R::Param>,
+    subject_query: Query<(Entity, &S), (Changed<S>, With<ObservedByResource<T, S, R>>)>,
+    ordering: Res<ObserverOrdering>,
+) {
+    let Some(mut resource) = resource else {
+        return;
+    };
+
+    let mut changed: Vec<_> = subject_query.iter().collect();
+    ordering.sort_by_entity(&mut changed, |(subject, _)| *subject);
+
+    for (subject, subject_comp) in changed {
+        let data = Subject::<T>::give_data(subject_comp);
+        resource.receive_data(data, &mut param, subject);
+    }
+}
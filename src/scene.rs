@@ -0,0 +1,533 @@
+use std::{
+    any::{type_name, TypeId},
+    marker::PhantomData,
+};
+
+use bevy::{
+    app::SystemAppConfig,
+    ecs::{
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        reflect::ReflectMapEntities,
+        schedule::SystemConfig,
+        system::Command,
+    },
+    prelude::*,
+    utils::{get_short_name, HashMap},
+};
+
+use crate::{
+    aggregate::{AggregateObserverBuildCommand, AggregateObserverRemoveCommand},
+    resource::{
+        ObservedByResource, ObservingResource, ResourceObserverBuildCommand,
+        ResourceObserverRemoveCommand,
+    },
+    two_way::{TwoWayBuildCommand, TwoWayRemoveCommand},
+    AggregateObserver, AggregateSubjects, Observer, ObserverRemoveCommand, ObserverSet,
+    ObservingList, ResourceObserver, ResourceObserverCommandExt, ResourceSubject, Subject,
+};
+
+/// What link components do with linked entities missing from the EntityMap,
+/// for example when a scene contains a subject but not all of its observers.
+/// Mapping always leaves those entities out of the link, so it never points at whatever entity
+/// reuses the original id. During ObserverSet::Cleanup they are then dropped for good, or linked
+/// again with Keep. Insert as a resource to change it. A warning is logged either way.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnmappedLinkPolicy {
+    /// Remove the link.
+    #[default]
+    Drop,
+    /// Keep the link pointing at the original entity.
+    Keep,
+}
+
+/// Maps every entity, returning the mapped entities and the ones missing from the map.
+pub(crate) fn map_links(
+    entities: impl IntoIterator<Item = Entity>,
+    m: &EntityMap,
+) -> (Vec<Entity>, Vec<Entity>) {
+    let mut mapped = Vec::new();
+    let mut unmapped = Vec::new();
+    for entity in entities {
+        match m.get(entity) {
+            Ok(entity) => mapped.push(entity),
+            Err(_) => unmapped.push(entity),
+        }
+    }
+    (mapped, unmapped)
+}
+
+/// Link components whose entities are mapped through MapEntities, such as by scenes.
+pub(crate) trait LinkEntities: Component {
+    /// Name of the link used in warnings.
+    const LINK: &'static str;
+
+    /// Entities the last mapping left out of the links, waiting for the UnmappedLinkPolicy.
+    fn unmapped(&mut self) -> &mut Vec<Entity>;
+
+    /// Links an unmapped entity again, for UnmappedLinkPolicy::Keep.
+    fn keep_link(&mut self, entity: Entity);
+
+    /// Unlinks an unmapped entity for good, for UnmappedLinkPolicy::Drop.
+    /// Nothing is left to do for links that leave unmapped entities out while mapping.
+    fn drop_link(&mut self, _entity: Entity) {}
+}
+
+/// Drops or keeps the entities left out when the L components were mapped.
+fn apply_unmapped_link_policy<L: LinkEntities>(
+    policy: Res<UnmappedLinkPolicy>,
+    mut links_query: Query<(Entity, &mut L), Changed<L>>,
+) {
+    for (entity, mut links) in links_query.iter_mut() {
+        if links.bypass_change_detection().unmapped().is_empty() {
+            continue;
+        }
+
+        for linked in std::mem::take(links.unmapped()) {
+            match *policy {
+                UnmappedLinkPolicy::Drop => {
+                    warn!(
+                        "Dropped {} link of {:?} to unmapped entity {:?}",
+                        L::LINK,
+                        entity,
+                        linked
+                    );
+                    links.drop_link(linked);
+                }
+                UnmappedLinkPolicy::Keep => {
+                    warn!(
+                        "Kept {} link of {:?} to unmapped entity {:?}",
+                        L::LINK,
+                        entity,
+                        linked
+                    );
+                    links.keep_link(linked);
+                }
+            }
+        }
+    }
+}
+
+/// Adds the pass applying the UnmappedLinkPolicy to L, with `place` deciding the schedule
+/// or base set it runs in.
+pub(crate) fn add_unmapped_link_policy<L: LinkEntities>(
+    app: &mut App,
+    place: impl Fn(SystemConfig) -> SystemAppConfig,
+) {
+    app.init_resource::<UnmappedLinkPolicy>().add_system(place(
+        apply_unmapped_link_policy::<L>
+            .in_set(ObserverSet::Cleanup)
+            .before(ObserverSet::Propagate),
+    ));
+}
+
+/// Links of an entity in a form that can be saved in scenes.
+/// Each link is keyed by the readable name its type was registered under, such as
+/// "Health -> HealthBar: f32", instead of the generic type path of its link component.
+/// Filled by store_scene_links, then turned back into links with an initial sync, and removed,
+/// once the entity is in an app with the same registrations.
+/// Links made with set_observer_with hold closures and are not saved.
+#[derive(Reflect, FromReflect, Clone, Default, Debug, Component)]
+#[reflect(Component, MapEntities)]
+pub struct SceneLinks {
+    pub links: Vec<SceneLink>,
+}
+
+/// One link type of an entity saved in SceneLinks.
+#[derive(Reflect, FromReflect, Clone, Default, Debug, PartialEq)]
+pub struct SceneLink {
+    /// Name the link type was registered under, such as "Health -> HealthBar: f32".
+    pub name: String,
+    /// Subjects observed by the entity through this link type.
+    pub subjects: Vec<Entity>,
+
+    /// Subjects the last mapping left out, waiting for the UnmappedLinkPolicy.
+    #[reflect(ignore)]
+    unmapped: Vec<Entity>,
+}
+
+impl SceneLink {
+    pub fn new(name: impl Into<String>, subjects: Vec<Entity>) -> Self {
+        SceneLink {
+            name: name.into(),
+            subjects,
+            unmapped: Vec::new(),
+        }
+    }
+}
+
+/// Subjects missing from the map are left out of the link, then dropped or kept according to
+/// the UnmappedLinkPolicy when the links are restored.
+impl MapEntities for SceneLinks {
+    fn map_entities(&mut self, m: &EntityMap) -> Result<(), MapEntitiesError> {
+        for link in self.links.iter_mut() {
+            let (mapped, unmapped) = map_links(link.subjects.drain(..), m);
+            link.subjects = mapped;
+            link.unmapped = unmapped;
+        }
+        Ok(())
+    }
+}
+
+/// How to save, restore and remove the links of one registered link type.
+#[derive(Clone, Copy)]
+pub(crate) struct SceneLinkType {
+    /// Component marking the entities that hold links of this type.
+    component: TypeId,
+    /// Every entity holding links of this type, with its subjects.
+    pub store: fn(&mut World) -> Vec<(Entity, Vec<Entity>)>,
+    /// Links the entity to the subjects and syncs it.
+    pub restore: fn(&mut World, Entity, Vec<Entity>),
+    /// Unlinks the entity from the subjects.
+    pub remove: fn(&mut World, Entity, Vec<Entity>),
+}
+
+/// Link types that can be saved in scenes or named in binding sets, by name.
+#[derive(Resource, Default)]
+struct SceneLinkTypes {
+    types: HashMap<String, SceneLinkType>,
+}
+
+/// The link type registered under the name, such as "Health -> HealthBar: f32".
+pub(crate) fn named_link_type(world: &World, name: &str) -> Option<SceneLinkType> {
+    world
+        .get_resource::<SceneLinkTypes>()
+        .and_then(|link_types| link_types.types.get(name).copied())
+}
+
+/// Makes the link type marked by component L savable in scenes under the name
+/// "S -> O: T", followed by the kind in brackets if there is one.
+/// Falls back to the full type name of L if another link type already uses that name.
+fn register_scene_link<T: 'static, S: 'static, O: 'static, L: Component>(
+    app: &mut App,
+    kind: Option<&str>,
+    store: fn(&mut World) -> Vec<(Entity, Vec<Entity>)>,
+    restore: fn(&mut World, Entity, Vec<Entity>),
+    remove: fn(&mut World, Entity, Vec<Entity>),
+) {
+    if !app.world.contains_resource::<SceneLinkTypes>() {
+        app.init_resource::<SceneLinkTypes>()
+            .init_resource::<UnmappedLinkPolicy>()
+            .register_type::<Entity>()
+            .register_type::<String>()
+            .register_type::<Vec<Entity>>()
+            .register_type::<SceneLink>()
+            .register_type::<Vec<SceneLink>>()
+            .register_type::<SceneLinks>()
+            .add_system(
+                restore_scene_links
+                    .in_base_set(CoreSet::PostUpdate)
+                    .in_set(ObserverSet::Cleanup)
+                    .before(ObserverSet::Propagate),
+            );
+    }
+
+    let link_type = SceneLinkType {
+        component: TypeId::of::<L>(),
+        store,
+        restore,
+        remove,
+    };
+    let mut name = format!(
+        "{} -> {}: {}",
+        get_short_name(type_name::<S>()),
+        get_short_name(type_name::<O>()),
+        get_short_name(type_name::<T>())
+    );
+    if let Some(kind) = kind {
+        name = format!("{} ({})", name, kind);
+    }
+
+    let mut link_types = app.world.resource_mut::<SceneLinkTypes>();
+    match link_types.types.get(&name) {
+        Some(existing) if existing.component == link_type.component => (),
+        Some(_) => {
+            let full_name = type_name::<L>().to_string();
+            warn!(
+                "Scene link name {} is already taken, saving {} under its full type name",
+                name, full_name
+            );
+            link_types.types.insert(full_name, link_type);
+        }
+        None => {
+            link_types.types.insert(name, link_type);
+        }
+    }
+}
+
+/// `restore` links an observer to its subjects again, which differs between plain and
+/// computed subjects.
+pub(crate) fn register_observer_scene_link<
+    T: Send + Sync + 'static,
+    S: Component,
+    O: Observer<T>,
+>(
+    app: &mut App,
+    restore: fn(&mut World, Entity, Vec<Entity>),
+) {
+    register_scene_link::<T, S, O, ObservingList<T, S, O>>(
+        app,
+        None,
+        |world| {
+            world
+                .query::<(Entity, &ObservingList<T, S, O>)>()
+                .iter(world)
+                .map(|(observer, list)| (observer, list.to_vec()))
+                .collect()
+        },
+        restore,
+        |world, observer, subjects| {
+            ObserverRemoveCommand::<T, S, O> {
+                observer,
+                subjects,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        },
+    );
+}
+
+pub(crate) fn register_two_way_scene_link<
+    T: PartialEq + Clone + Send + Sync + 'static,
+    S: Subject<T> + Observer<T>,
+    O: Subject<T> + Observer<T>,
+>(
+    app: &mut App,
+) {
+    register_scene_link::<T, S, O, ObservingList<T, S, O>>(
+        app,
+        Some("two-way"),
+        |world| {
+            world
+                .query::<(Entity, &ObservingList<T, S, O>)>()
+                .iter(world)
+                .map(|(observer, list)| (observer, list.to_vec()))
+                .collect()
+        },
+        |world, observer, subjects| {
+            TwoWayBuildCommand::<T, S, O> {
+                observer,
+                subjects,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        },
+        |world, observer, subjects| {
+            TwoWayRemoveCommand::<T, S, O> {
+                observer,
+                subjects,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        },
+    );
+}
+
+pub(crate) fn register_aggregate_scene_link<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    O: AggregateObserver<T>,
+>(
+    app: &mut App,
+) {
+    register_scene_link::<T, S, O, AggregateSubjects<T, S, O>>(
+        app,
+        Some("aggregate"),
+        |world| {
+            world
+                .query::<(Entity, &AggregateSubjects<T, S, O>)>()
+                .iter(world)
+                .map(|(observer, aggregate)| (observer, aggregate.iter().copied().collect()))
+                .collect()
+        },
+        |world, observer, subjects| {
+            AggregateObserverBuildCommand::<T, S, O> {
+                observer,
+                subjects,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        },
+        |world, observer, subjects| {
+            AggregateObserverRemoveCommand::<T, S, O> {
+                observer,
+                subjects,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        },
+    );
+}
+
+pub(crate) fn register_resource_scene_link<
+    T: Send + Sync + 'static,
+    R: ResourceSubject<T>,
+    O: Observer<T>,
+>(
+    app: &mut App,
+) {
+    register_scene_link::<T, R, O, ObservingResource<T, R, O>>(
+        app,
+        Some("resource"),
+        |world| {
+            world
+                .query_filtered::<Entity, With<ObservingResource<T, R, O>>>()
+                .iter(world)
+                .map(|observer| (observer, Vec::new()))
+                .collect()
+        },
+        |world, observer, _| {
+            ResourceObserverBuildCommand::<T, R, O> {
+                observer,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        },
+        |world, observer, _| {
+            ResourceObserverRemoveCommand::<T, R, O> {
+                observer,
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world)
+        },
+    );
+}
+
+/// Resources aren't saved in scenes, so the link is kept on the subject entity, observing itself.
+pub(crate) fn register_resource_as_observer_scene_link<
+    T: Send + Sync + 'static,
+    S: Subject<T>,
+    R: ResourceObserver<T>,
+>(
+    app: &mut App,
+) {
+    register_scene_link::<T, S, R, ObservedByResource<T, S, R>>(
+        app,
+        Some("resource observer"),
+        |world| {
+            world
+                .query_filtered::<Entity, With<ObservedByResource<T, S, R>>>()
+                .iter(world)
+                .map(|subject| (subject, vec![subject]))
+                .collect()
+        },
+        |world, _, subjects| {
+            world.set_resource_as_observer::<T, S, R>(subjects);
+        },
+        |world, _, subjects| {
+            world.remove_resource_as_observer::<T, S, R>(subjects);
+        },
+    );
+}
+
+pub trait SceneLinksExt {
+    /// Writes the links of every entity into its SceneLinks, replacing the old ones,
+    /// so that scenes built from the world afterwards carry them.
+    fn store_scene_links(&mut self) -> &mut Self;
+}
+
+impl SceneLinksExt for World {
+    fn store_scene_links(&mut self) -> &mut Self {
+        let stale: Vec<Entity> = self
+            .query_filtered::<Entity, With<SceneLinks>>()
+            .iter(self)
+            .collect();
+        for entity in stale {
+            self.entity_mut(entity).remove::<SceneLinks>();
+        }
+
+        let Some(link_types) = self.get_resource::<SceneLinkTypes>() else {
+            return self;
+        };
+        let mut link_types: Vec<(String, SceneLinkType)> = link_types
+            .types
+            .iter()
+            .map(|(name, link_type)| (name.clone(), *link_type))
+            .collect();
+        link_types.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (name, link_type) in link_types {
+            for (entity, subjects) in (link_type.store)(self) {
+                let link = SceneLink::new(name.clone(), subjects);
+                let mut entity_mut = self.entity_mut(entity);
+                match entity_mut.get_mut::<SceneLinks>() {
+                    Some(mut scene_links) => scene_links.links.push(link),
+                    None => {
+                        entity_mut.insert(SceneLinks { links: vec![link] });
+                    }
+                }
+            }
+        }
+
+        self
+    }
+}
+
+struct RestoreSceneLinksCommand {
+    entity: Entity,
+    links: Vec<SceneLink>,
+}
+
+impl Command for RestoreSceneLinksCommand {
+    fn write(self, world: &mut World) {
+        if world.get_entity(self.entity).is_none() {
+            return;
+        }
+        let policy = world
+            .get_resource::<UnmappedLinkPolicy>()
+            .copied()
+            .unwrap_or_default();
+
+        for link in self.links {
+            let Some(link_type) = named_link_type(world, &link.name) else {
+                warn!(
+                    "Dropped scene link {} of {:?}, no link type is registered under that name",
+                    link.name, self.entity
+                );
+                continue;
+            };
+
+            let mut subjects = link.subjects;
+            for subject in link.unmapped {
+                match policy {
+                    UnmappedLinkPolicy::Drop => warn!(
+                        "Dropped {} link of {:?} to unmapped entity {:?}",
+                        link.name, self.entity, subject
+                    ),
+                    UnmappedLinkPolicy::Keep => {
+                        warn!(
+                            "Kept {} link of {:?} to unmapped entity {:?}",
+                            link.name, self.entity, subject
+                        );
+                        subjects.push(subject);
+                    }
+                }
+            }
+            (link_type.restore)(world, self.entity, subjects);
+        }
+    }
+}
+
+/// Restores the links of every entity with SceneLinks, such as those spawned from a scene.
+fn restore_scene_links(mut commands: Commands, query: Query<(Entity, &SceneLinks)>) {
+    for (entity, scene_links) in query.iter() {
+        commands.add(RestoreSceneLinksCommand {
+            entity,
+            links: scene_links.links.clone(),
+        });
+        commands.entity(entity).remove::<SceneLinks>();
+    }
+}
@@ -0,0 +1,228 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{
+        query::QueryEntityError,
+        system::{Command, StaticSystemParam},
+    },
+    prelude::*,
+};
+
+use crate::{
+    insert_sorted, Observer, ObserverBuildCommand, ObserverList, ObserverOrdering,
+    ObserverRemoveCommand, ObservingList, Subject,
+};
+
+/// Last value the A component of this entity is known to hold, for its two-way links with B.
+/// A change is only passed on to sides that don't hold it yet, so the echo stops there.
+/// Kept apart from the last_sent of ObserverList, which belongs to diffing.
+#[derive(Component)]
+pub(crate) struct TwoWayValue<T: Send + Sync + 'static, A: 'static, B: 'static> {
+    value: Option<T>,
+
+    phantom_subject: PhantomData<A>,
+    phantom_observer: PhantomData<B>,
+}
+
+impl<T: Send + Sync + 'static, A: 'static, B: 'static> TwoWayValue<T, A, B> {
+    fn new(value: Option<T>) -> Self {
+        TwoWayValue {
+            value,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+    }
+}
+
+/// Links S on the subjects and O on the observer both ways.
+/// Each side keeps the last value it is known to hold in a TwoWayValue.
+pub(crate) struct TwoWayBuildCommand<
+    T: PartialEq + Clone + Send + Sync + 'static,
+    S: Subject<T> + Observer<T>,
+    O: Subject<T> + Observer<T>,
+> {
+    pub observer: Entity,
+    pub subjects: Vec<Entity>,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<S>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    > Command for TwoWayBuildCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        // The forward link syncs O from S as usual.
+        ObserverBuildCommand::<T, S, O> {
+            observer: self.observer,
+            subjects: self.subjects.clone(),
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+        .write(world);
+
+        // The link back is added by hand, so that it neither syncs S from O nor warns about
+        // closing a cycle with the forward link.
+        for &source in self.subjects.iter() {
+            let known = world
+                .get::<S>(source)
+                .map(|subject_comp| Subject::<T>::give_data(subject_comp).clone());
+
+            let Some(mut entity_mut) = world.get_entity_mut(source) else {
+                continue;
+            };
+            entity_mut.insert(TwoWayValue::<T, S, O>::new(known));
+            match entity_mut.get_mut::<ObservingList<T, O, S>>() {
+                Some(mut observing_list) => {
+                    insert_sorted(&mut observing_list.subjects, self.observer);
+                }
+                None => {
+                    entity_mut.insert(ObservingList::<T, O, S>::new(vec![self.observer]));
+                }
+            }
+        }
+
+        let known = world
+            .get::<O>(self.observer)
+            .map(|observer_comp| Subject::<T>::give_data(observer_comp).clone());
+        let Some(mut entity_mut) = world.get_entity_mut(self.observer) else {
+            return;
+        };
+        entity_mut.insert(TwoWayValue::<T, O, S>::new(known));
+        match entity_mut.get_mut::<ObserverList<T, O, S>>() {
+            Some(mut observer_list) => {
+                for &source in self.subjects.iter() {
+                    observer_list.insert(source);
+                }
+            }
+            None => {
+                entity_mut.insert(ObserverList::<T, O, S>::new(self.subjects));
+            }
+        }
+    }
+}
+
+pub(crate) struct TwoWayRemoveCommand<
+    T: PartialEq + Clone + Send + Sync + 'static,
+    S: Subject<T> + Observer<T>,
+    O: Subject<T> + Observer<T>,
+> {
+    pub observer: Entity,
+    pub subjects: Vec<Entity>,
+    pub phantom_data: PhantomData<T>,
+    pub phantom_subject: PhantomData<S>,
+    pub phantom_observer: PhantomData<O>,
+}
+
+impl<
+        T: PartialEq + Clone + Send + Sync + 'static,
+        S: Subject<T> + Observer<T>,
+        O: Subject<T> + Observer<T>,
+    > Command for TwoWayRemoveCommand<T, S, O>
+{
+    fn write(self, world: &mut World) {
+        for &source in self.subjects.iter() {
+            ObserverRemoveCommand::<T, O, S> {
+                observer: source,
+                subjects: vec![self.observer],
+                phantom_data: PhantomData,
+                phantom_subject: PhantomData,
+                phantom_observer: PhantomData,
+            }
+            .write(world);
+        }
+
+        ObserverRemoveCommand::<T, S, O> {
+            observer: self.observer,
+            subjects: self.subjects.clone(),
+            phantom_data: PhantomData,
+            phantom_subject: PhantomData,
+            phantom_observer: PhantomData,
+        }
+        .write(world);
+
+        // Sides left without two-way links forget their value.
+        for &source in self.subjects.iter() {
+            forget_unlinked_value::<T, S, O>(world, source);
+        }
+        forget_unlinked_value::<T, O, S>(world, self.observer);
+    }
+}
+
+/// Removes the TwoWayValue of the A side of the entity once it has no links with B left.
+fn forget_unlinked_value<
+    T: PartialEq + Clone + Send + Sync + 'static,
+    A: Subject<T> + Observer<T>,
+    B: Subject<T> + Observer<T>,
+>(
+    world: &mut World,
+    entity: Entity,
+) {
+    let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if !entity_mut.contains::<ObserverList<T, A, B>>() {
+        entity_mut.remove::<TwoWayValue<T, A, B>>();
+    }
+}
+
+/// Sends changed A on subjects to B on their observers, skipping observers whose last known
+/// value is already the one sent. Two-way links run this in both directions.
+pub(crate) fn recieve_two_way_event<
+    T: PartialEq + Clone + Send + Sync + 'static,
+    A: Subject<T> + Observer<T>,
+    B: Subject<T> + Observer<T>,
+>(
+    ordering: Res<ObserverOrdering>,
+    mut param: StaticSystemParam<B::Param>,
+    mut observer_query: Query<(&mut B, Option<&mut TwoWayValue<T, B, A>>)>,
+    mut observer_list_query: Query<
+        (
+            Entity,
+            &A,
+            &mut ObserverList<T, A, B>,
+            Option<&mut TwoWayValue<T, A, B>>,
+        ),
+        Changed<A>,
+    >,
+) {
+    let mut changed: Vec<_> = observer_list_query.iter_mut().collect();
+    ordering.sort_by_entity(&mut changed, |(subject, ..)| *subject);
+
+    for (subject, subject_comp, mut observer_list, subject_known) in changed {
+        let data = Subject::<T>::give_data(subject_comp);
+        if let Some(mut subject_known) = subject_known {
+            subject_known.value = Some(data.clone());
+        }
+
+        let mut observers = observer_list.observers.clone();
+        ordering.sort_by_entity(&mut observers, |observer| *observer);
+
+        let mut remove_list = Vec::<Entity>::new();
+        for observer in observers {
+            match observer_query.get_mut(observer) {
+                Ok((mut observer_comp, observer_known)) => {
+                    let known = observer_known
+                        .as_ref()
+                        .and_then(|observer_known| observer_known.value.as_ref());
+                    if known == Some(data) {
+                        continue;
+                    }
+                    observer_comp.receive_data(data, &mut param, subject);
+                    if let Some(mut observer_known) = observer_known {
+                        observer_known.value =
+                            Some(Subject::<T>::give_data(&*observer_comp).clone());
+                    }
+                }
+                Err(QueryEntityError::NoSuchEntity { .. }) => remove_list.push(observer),
+                _ => (),
+            }
+        }
+
+        observer_list.observers.retain(|x| !remove_list.contains(x));
+    }
+}